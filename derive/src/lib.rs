@@ -0,0 +1,141 @@
+//! The derive macro behind the `derive` feature of
+//! [`get_mut_drop_weak`](https://docs.rs/get_mut_drop_weak). Use it through
+//! that crate's `GetMutAll` re-export.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{
+    Data, DeriveInput, Error, Fields, GenericArgument, Lifetime, LifetimeParam, PathArguments,
+    Type, parse_macro_input,
+};
+
+/// Generates `get_mut_all(&mut self)` for a struct whose fields are all
+/// reference-counted pointers, e.g. `Arc<_>` or `Rc<_>`, along with a struct
+/// named after it with a `Mut` suffix holding `&mut` references to their
+/// values.
+///
+/// The method makes every field exclusive with `get_mut_drop_weak`, all or
+/// nothing, and otherwise returns a `FieldBlocked` error naming the first
+/// field that blocked. A strongly shared field blocks before any field is
+/// touched.
+///
+/// ```
+/// use std::{rc::Rc, sync::Arc};
+/// use get_mut_drop_weak::GetMutAll;
+///
+/// #[derive(GetMutAll)]
+/// struct State {
+///     names: Arc<Vec<String>>,
+///     count: Rc<usize>,
+/// }
+///
+/// let mut state = State {
+///     names: Arc::new(Vec::new()),
+///     count: Rc::new(0),
+/// };
+/// let other = Rc::clone(&state.count);
+/// assert_eq!(state.get_mut_all().err().unwrap().field(), "count");
+///
+/// drop(other);
+/// let StateMut { names, count } = state.get_mut_all().unwrap();
+/// names.push(String::from("a"));
+/// *count += 1;
+/// ```
+#[proc_macro_derive(GetMutAll)]
+pub fn derive_get_mut_all(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "GetMutAll requires a struct with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "GetMutAll can only be derived for structs",
+            ));
+        }
+    };
+    if fields.is_empty() {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "GetMutAll requires at least one field",
+        ));
+    }
+
+    let names: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+    let vises: Vec<_> = fields.iter().map(|field| &field.vis).collect();
+    let values = fields
+        .iter()
+        .map(|field| pointee(&field.ty))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let mut_ident = format_ident!("{}Mut", ident);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let lifetime = Lifetime::new("'__get_mut", Span::call_site());
+    let mut mut_generics = input.generics.clone();
+    mut_generics
+        .params
+        .insert(0, LifetimeParam::new(lifetime.clone()).into());
+    let (_, mut_ty_generics, _) = mut_generics.split_for_impl();
+
+    let krate = quote!(::get_mut_drop_weak);
+    let mut_doc = format!("Exclusive access to the values of a [`{ident}`], from `get_mut_all`.");
+
+    Ok(quote! {
+        #[doc = #mut_doc]
+        #vis struct #mut_ident #mut_generics #where_clause {
+            #(#vises #names: &#lifetime mut #values,)*
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Makes every field exclusive with `get_mut_drop_weak`, all or
+            /// nothing, orphaning their weak pointers.
+            ///
+            /// Returns an error naming the first field that blocked. If it
+            /// was strongly shared, no field was touched.
+            #vis fn get_mut_all<#lifetime>(
+                &#lifetime mut self,
+            ) -> ::core::result::Result<#mut_ident #mut_ty_generics, #krate::FieldBlocked> {
+                let Self { #(#names),* } = self;
+                #(#krate::__private::check_strong(&*#names, ::core::stringify!(#names))?;)*
+                #(#krate::__private::make_exclusive(&mut *#names, ::core::stringify!(#names))?;)*
+                ::core::result::Result::Ok(#mut_ident {
+                    #(#names: #krate::__private::exclusive(#names),)*
+                })
+            }
+        }
+    })
+}
+
+/// The type argument of a pointer type such as `Arc<T>`.
+fn pointee(ty: &Type) -> syn::Result<&Type> {
+    if let Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                if let Some(GenericArgument::Type(pointee)) = args.args.first() {
+                    return Ok(pointee);
+                }
+            }
+        }
+    }
+    Err(Error::new_spanned(
+        ty,
+        "GetMutAll fields must be pointers such as `Arc<T>` or `Rc<T>`",
+    ))
+}
@@ -0,0 +1,82 @@
+use core::ptr;
+
+use crate::{SharedPointer, get_mut_drop_weak};
+
+/// The outcome of [`get_mut_drop_weak_access`].
+#[derive(Debug)]
+pub enum Access<'a, T, P> {
+    /// Exclusive access to the original allocation. There were no weak
+    /// pointers.
+    Unchanged(&'a mut T),
+    /// Exclusive access to a new allocation. Weak pointers were orphaned, and
+    /// anything keyed by the old address (e.g. `Arc::as_ptr`) is stale.
+    Replaced(&'a mut T),
+    /// The pointer is strongly shared, or a concurrent weak upgrade won the
+    /// race. The pointer is handed back unchanged.
+    Shared(&'a mut P),
+}
+
+impl<'a, T, P> Access<'a, T, P> {
+    /// Whether the allocation was swapped.
+    pub fn is_replaced(&self) -> bool {
+        matches!(self, Access::Replaced(_))
+    }
+
+    /// Converts into the result [`get_mut_drop_weak`] would have returned.
+    pub fn into_result(self) -> Result<&'a mut T, &'a mut P> {
+        match self {
+            Access::Unchanged(value) | Access::Replaced(value) => Ok(value),
+            Access::Shared(ptr) => Err(ptr),
+        }
+    }
+}
+
+/// Like [`get_mut_drop_weak`], but also reports whether the allocation was
+/// replaced, so that callers can invalidate state keyed by its address.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_access<T, P: SharedPointer<T>>(ptr: &mut P) -> Access<'_, T, P> {
+    let original = P::as_ptr(ptr);
+    match get_mut_drop_weak(ptr) {
+        // The replacement is allocated while the original is still alive, so
+        // the addresses can't coincide.
+        Ok(value) if ptr::eq(value, original) => Access::Unchanged(value),
+        Ok(value) => Access::Replaced(value),
+        Err(ptr) => Access::Shared(ptr),
+    }
+}
+
+/// Like [`get_mut_drop_weak`], but also returns how many weak pointers were
+/// orphaned by the replacement, or 0 if the allocation was kept.
+///
+/// The count is read just before the replacement. Other threads cloning or
+/// dropping weak pointers at the same time can make it slightly off.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_counted<T, P: SharedPointer<T>>(
+    ptr: &mut P,
+) -> Result<(&mut T, usize), &mut P> {
+    let weak = P::weak_count(ptr);
+    match get_mut_drop_weak_access(ptr) {
+        Access::Unchanged(value) => Ok((value, 0)),
+        Access::Replaced(value) => Ok((value, weak)),
+        Access::Shared(ptr) => Err(ptr),
+    }
+}
+
+/// Like [`get_mut_drop_weak`], but if the allocation was replaced, also
+/// returns the address of the old value (as given by `Arc::as_ptr`), so that
+/// side tables keyed by it can be migrated.
+///
+/// The old address must not be dereferenced: the value has moved to the new
+/// allocation, and the old one is freed once the orphaned weak pointers are
+/// dropped. It may be reused by a later allocation.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_with_old<T, P: SharedPointer<T>>(
+    ptr: &mut P,
+) -> Result<(&mut T, Option<*const T>), &mut P> {
+    let original = P::as_ptr(ptr);
+    match get_mut_drop_weak_access(ptr) {
+        Access::Unchanged(value) => Ok((value, None)),
+        Access::Replaced(value) => Ok((value, Some(original))),
+        Access::Shared(ptr) => Err(ptr),
+    }
+}
@@ -0,0 +1,156 @@
+use alloc::vec::Vec;
+
+use crate::{
+    FailureReason, FieldBlocked, SharedPointer, get_mut_drop_weak, get_mut_drop_weak_detailed,
+};
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak) for two pointers at
+/// once, succeeding for both or for neither.
+///
+/// Both strong counts are checked before either pointer is touched, so if
+/// either is strongly shared, both are handed back unchanged and no weak
+/// pointer is orphaned.
+///
+/// A concurrent weak upgrade can still win the race against the second
+/// replacement after the first one went through. The first pointer then
+/// keeps its value, but its weak pointers are orphaned, which can't be
+/// undone. Both pointers are handed back in that case too, so no partial
+/// access is ever returned.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak2<'a, A, B, P: SharedPointer<A>, Q: SharedPointer<B>>(
+    a: &'a mut P,
+    b: &'a mut Q,
+) -> Result<(&'a mut A, &'a mut B), (&'a mut P, &'a mut Q)> {
+    (a, b).get_mut_drop_weak_all()
+}
+
+/// Mutable references to the values of all of `ptrs`, if every pointer is
+/// already exclusive: the only strong pointer, with no weak pointers.
+///
+/// Unlike [`get_mut_drop_weak_batch`](crate::get_mut_drop_weak_batch), this
+/// never replaces an allocation, so it's a cheap way to assert ownership of
+/// the whole set. Returns None, touching nothing, if any pointer is shared.
+pub fn get_many_mut<T, P: SharedPointer<T>>(ptrs: &mut [P]) -> Option<Vec<&mut T>> {
+    if !ptrs.iter_mut().all(P::is_unique) {
+        return None;
+    }
+    Some(ptrs.iter_mut().map(exclusive).collect())
+}
+
+/// A tuple of mutable references to pointers, all made exclusive at once
+/// with [`get_mut_drop_weak_all`](Self::get_mut_drop_weak_all) or the
+/// [`get_mut_all!`](crate::get_mut_all) macro.
+///
+/// Implemented for tuples of 1 to 8 `&mut P`, where each `P` is a
+/// [`SharedPointer`] and the types may differ.
+/// `Values` is the matching tuple of mutable references to the pointers'
+/// values.
+pub trait PointerTuple<Values>: Sized {
+    /// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak) for every
+    /// pointer, succeeding for all or for none, as [`get_mut_drop_weak2`]
+    /// does for two.
+    ///
+    /// Returns the pointers back if any of them was strongly shared, in which
+    /// case none was touched, or if a concurrent weak upgrade won a race, in
+    /// which case the pointers before it may have had their weak pointers
+    /// orphaned.
+    fn get_mut_drop_weak_all(self) -> Result<Values, Self>;
+}
+
+macro_rules! impl_pointer_tuple {
+    ($($T:ident $P:ident $p:ident),+) => {
+        impl<'a, $($T, $P: SharedPointer<$T>),+> PointerTuple<($(&'a mut $T,)+)>
+            for ($(&'a mut $P,)+)
+        {
+            #[cfg_attr(feature = "call-sites", track_caller)]
+            fn get_mut_drop_weak_all(self) -> Result<($(&'a mut $T,)+), Self> {
+                let ($($p,)+) = self;
+                if $($P::strong_count($p) > 1)||+ {
+                    return Err(($($p,)+));
+                }
+                if $(get_mut_drop_weak(&mut *$p).is_err())||+ {
+                    return Err(($($p,)+));
+                }
+                Ok(($(exclusive($p),)+))
+            }
+        }
+    };
+}
+
+impl_pointer_tuple!(T0 P0 p0);
+impl_pointer_tuple!(T0 P0 p0, T1 P1 p1);
+impl_pointer_tuple!(T0 P0 p0, T1 P1 p1, T2 P2 p2);
+impl_pointer_tuple!(T0 P0 p0, T1 P1 p1, T2 P2 p2, T3 P3 p3);
+impl_pointer_tuple!(T0 P0 p0, T1 P1 p1, T2 P2 p2, T3 P3 p3, T4 P4 p4);
+impl_pointer_tuple!(T0 P0 p0, T1 P1 p1, T2 P2 p2, T3 P3 p3, T4 P4 p4, T5 P5 p5);
+impl_pointer_tuple!(T0 P0 p0, T1 P1 p1, T2 P2 p2, T3 P3 p3, T4 P4 p4, T5 P5 p5, T6 P6 p6);
+impl_pointer_tuple!(T0 P0 p0, T1 P1 p1, T2 P2 p2, T3 P3 p3, T4 P4 p4, T5 P5 p5, T6 P6 p6, T7 P7 p7);
+
+/// Makes up to 8 pointers exclusive at once, all or nothing, with
+/// [`PointerTuple::get_mut_drop_weak_all`].
+///
+/// Takes `&mut` expressions for any mix of [`SharedPointer`] types, and
+/// evaluates to `Ok` with a tuple of mutable references to their values, or
+/// `Err` with the tuple of pointers.
+///
+/// ```
+/// use std::sync::Arc;
+/// use get_mut_drop_weak::get_mut_all;
+///
+/// let mut a = Arc::new(1);
+/// let mut b = Arc::new(String::from("b"));
+/// let mut c = Arc::new(vec![3]);
+///
+/// let Ok((a, b, c)) = get_mut_all!(&mut a, &mut b, &mut c) else {
+///     panic!("shared");
+/// };
+/// *a += 1;
+/// b.push('!');
+/// c.push(4);
+/// ```
+#[macro_export]
+macro_rules! get_mut_all {
+    ($($ptr:expr),+ $(,)?) => {
+        $crate::PointerTuple::get_mut_drop_weak_all(($($ptr,)+))
+    };
+}
+
+/// Fails with [`FailureReason::StronglyShared`] if `ptr` is strongly shared.
+/// Used by the code generated by [`GetMutAll`](crate::GetMutAll).
+#[doc(hidden)]
+pub fn check_strong<T, P: SharedPointer<T>>(
+    ptr: &P,
+    field: &'static str,
+) -> Result<(), FieldBlocked> {
+    if P::strong_count(ptr) > 1 {
+        return Err(FieldBlocked::new(field, FailureReason::StronglyShared));
+    }
+    Ok(())
+}
+
+/// [`get_mut_drop_weak`], with the failure attributed to `field`. Used by the
+/// code generated by [`GetMutAll`](crate::GetMutAll).
+#[cfg_attr(feature = "call-sites", track_caller)]
+#[doc(hidden)]
+pub fn make_exclusive<T, P: SharedPointer<T>>(
+    ptr: &mut P,
+    field: &'static str,
+) -> Result<(), FieldBlocked> {
+    match get_mut_drop_weak_detailed(ptr) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(FieldBlocked::new(field, error.reason())),
+    }
+}
+
+/// Exclusive access to a pointer that [`get_mut_drop_weak`] just succeeded
+/// on. Used by the code generated by [`GetMutAll`](crate::GetMutAll).
+///
+/// # Panics
+///
+/// Panics if `ptr` isn't the only pointer to its value. The counts are
+/// checked even though the caller already made it exclusive, as this is
+/// callable from safe code in any crate.
+#[doc(hidden)]
+pub fn exclusive<T, P: SharedPointer<T>>(ptr: &mut P) -> &mut T {
+    P::get_mut(ptr).expect("pointer was made exclusive")
+}
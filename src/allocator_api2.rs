@@ -0,0 +1,82 @@
+//! Allocator-parameterized entry point for `Arc` implementations built on
+//! [`allocator_api2`], for custom allocators on stable.
+
+use allocator_api2::alloc::Allocator;
+
+use crate::instrument;
+
+/// A reference-counted pointer whose replacement can be allocated in a
+/// caller-supplied [`Allocator`].
+///
+/// Implement this for your `allocator_api2`-based `Arc` to use it with
+/// [`get_mut_drop_weak_in`].
+///
+/// # Safety
+/// The counts and pointers reported by the implementation must be accurate:
+/// [`strong_count`](Self::strong_count) must not under-report,
+/// [`is_unique`](Self::is_unique) must only return true when there are no
+/// other strong or weak pointers, and [`as_ptr`](Self::as_ptr) must point to
+/// the value owned by the pointer. [`init`](Self::init) must not panic.
+pub unsafe trait AllocatorArc<T>: Sized {
+    /// The allocator replacements are allocated in.
+    type Allocator: Allocator;
+    /// Storage for a not-yet-initialized replacement pointer.
+    type Uninit;
+
+    /// Like `Arc::as_ptr`.
+    fn as_ptr(this: &Self) -> *const T;
+    /// Like `Arc::strong_count`.
+    fn strong_count(this: &Self) -> usize;
+    /// Like `Arc::weak_count`. Only reported to the instrumentation features.
+    fn weak_count(this: &Self) -> usize;
+    /// Like `Arc::get_mut(this).is_some()`.
+    fn is_unique(this: &mut Self) -> bool;
+    /// Like `Arc::try_unwrap`.
+    fn try_unwrap(this: Self) -> Result<T, Self>;
+    /// Like `Arc::new_uninit_in`.
+    fn new_uninit_in(alloc: Self::Allocator) -> Self::Uninit;
+
+    /// Writes `value` into `uninit`, like `Arc::assume_init` after a write.
+    ///
+    /// # Safety
+    /// `uninit` must have been created by [`AllocatorArc::new_uninit_in`] and
+    /// must not have been shared since.
+    unsafe fn init(uninit: Self::Uninit, value: T) -> Self;
+}
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but allocates the
+/// replacement (if one is needed) in `alloc`.
+///
+/// `alloc` is dropped unused if no replacement is needed.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_in<T, P: AllocatorArc<T>>(
+    ptr: &mut P,
+    alloc: P::Allocator,
+) -> Result<&mut T, &mut P> {
+    if P::is_unique(ptr) {
+        instrument::fast_path();
+        return Ok(unsafe { &mut *P::as_ptr(ptr).cast_mut() });
+    }
+    let strong = P::strong_count(ptr);
+    if strong > 1 {
+        instrument::strongly_shared::<T>(strong, P::weak_count(ptr));
+        return Err(ptr);
+    }
+
+    // Allocate first so that an allocation failure panics before `ptr` is touched.
+    let preallocated = P::new_uninit_in(alloc);
+    let orphaned = P::weak_count(ptr);
+
+    // SAFETY: `preallocated` came straight from `new_uninit_in` and was never shared.
+    if unsafe {
+        crate::replace(ptr, preallocated, P::try_unwrap, |u, value| {
+            P::init(u, value)
+        })
+    } {
+        instrument::replaced::<T>(orphaned);
+        Ok(unsafe { &mut *P::as_ptr(ptr).cast_mut() })
+    } else {
+        instrument::lost_race::<T>(P::strong_count(ptr), P::weak_count(ptr));
+        Err(ptr)
+    }
+}
@@ -0,0 +1,57 @@
+use alloc::sync::Arc;
+use core::any::Any;
+
+use crate::{Sealed, guard::moved_out, instrument};
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but for type-erased
+/// `Arc<dyn Any + Send + Sync>`, downcasting to `T`.
+///
+/// If only weak pointers block exclusive access, the concrete value is moved
+/// into a new `Arc` which is stored back in `arc` (still as
+/// `Arc<dyn Any + Send + Sync>`), orphaning the weak pointers.
+///
+/// Returns Err(&mut Arc<dyn Any + Send + Sync>) if the value is not a `T`, if
+/// the strong count was greater than 1, or if a concurrent weak upgrade won
+/// the race. Use `arc.is::<T>()` to tell these apart.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_any<T: Any + Send + Sync>(
+    arc: &mut Arc<dyn Any + Send + Sync>,
+) -> Result<&mut T, &mut Arc<dyn Any + Send + Sync>> {
+    if !(**arc).is::<T>() {
+        return Err(arc);
+    }
+    if Arc::get_mut(arc).is_some() {
+        instrument::fast_path();
+    } else {
+        let strong = Arc::strong_count(arc);
+        if strong > 1 {
+            instrument::strongly_shared::<T>(strong, Arc::weak_count(arc));
+            return Err(arc);
+        }
+
+        // Allocate first so that an allocation failure panics before `arc` is touched.
+        let preallocated = Arc::<T>::new_uninit();
+        let orphaned = Arc::weak_count(arc);
+
+        // SAFETY: We checked the type above.
+        // `preallocated` came straight from `new_uninit` and was never shared.
+        let replaced = unsafe {
+            moved_out(arc, |erased| {
+                let mut typed = erased.downcast::<T>().unwrap_unchecked();
+                let replaced =
+                    crate::replace(&mut typed, preallocated, Arc::try_unwrap, |u, value| {
+                        Sealed::init(u, value)
+                    });
+                (typed as Arc<dyn Any + Send + Sync>, replaced)
+            })
+        };
+        if !replaced {
+            instrument::lost_race::<T>(Arc::strong_count(arc), Arc::weak_count(arc));
+            return Err(arc);
+        }
+        instrument::replaced::<T>(orphaned);
+    }
+    // SAFETY: `arc` is exclusive, either already or because it was just
+    // replaced, and holds a `T`.
+    Ok(unsafe { &mut *Arc::as_ptr(arc).cast::<T>().cast_mut() })
+}
@@ -0,0 +1,30 @@
+use alloc::sync::Arc;
+
+use arc_swap::{ArcSwap, Guard};
+
+/// Mutates the value in `swap` with `f`, retrying until no other writer
+/// changed it in the meantime, and returns the result of the last call to `f`.
+///
+/// The value in an `ArcSwap` is never exclusively owned: the swap holds a
+/// strong pointer itself, and guards returned by [`ArcSwap::load`] don't even
+/// show up in the strong count. A strong count of 2, the swap's and a loaded
+/// copy's, therefore doesn't rule out readers, and the value can't be handed
+/// to [`get_mut_drop_weak`](crate::get_mut_drop_weak) while the swap holds
+/// it. So each attempt clones the current value, calls `f` on the copy, and
+/// compare-and-swaps it in. Weak pointers to the
+/// old value die with it once its last reader lets go. Unlike
+/// [`ArcSwap::rcu`], `f` mutates in place instead of building a new value.
+///
+/// Requires the `arc-swap` feature.
+pub fn rcu_mut_drop_weak<T: Clone, R>(swap: &ArcSwap<T>, mut f: impl FnMut(&mut T) -> R) -> R {
+    let mut current = swap.load_full();
+    loop {
+        let mut value = T::clone(&current);
+        let result = f(&mut value);
+        let previous = swap.compare_and_swap(&current, Arc::new(value));
+        if Arc::ptr_eq(&current, &previous) {
+            return result;
+        }
+        current = Guard::into_inner(previous);
+    }
+}
@@ -0,0 +1,142 @@
+use alloc::{
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{fmt, ops::Deref};
+use std::{
+    backtrace::Backtrace,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{Access, get_mut_drop_weak_access};
+
+type Origins = Mutex<Vec<Weak<Backtrace>>>;
+
+/// An [`Arc`] that captures a backtrace for each weak pointer created from
+/// it, to find out who keeps forcing
+/// [`get_mut_drop_weak`](crate::get_mut_drop_weak) to replace the allocation.
+///
+/// Weak handles are [`AuditedWeak`]s, which keep their backtrace alive. The
+/// backtraces of the handles still alive are listed by
+/// [`AuditedArc::live_origins`]. Capturing a backtrace is slow, so this is
+/// meant for debugging only.
+///
+/// Requires the `weak-audit` feature.
+pub struct AuditedArc<T> {
+    arc: Arc<T>,
+    origins: Arc<Origins>,
+}
+
+/// A weak handle to an [`AuditedArc`], carrying the backtrace of its
+/// creation.
+pub struct AuditedWeak<T> {
+    weak: Weak<T>,
+    origins: Arc<Origins>,
+    origin: Arc<Backtrace>,
+}
+
+impl<T> AuditedArc<T> {
+    /// Allocates `value` in a new `Arc`.
+    pub fn new(value: T) -> Self {
+        Self {
+            arc: Arc::new(value),
+            origins: Arc::default(),
+        }
+    }
+
+    /// Creates a weak handle, capturing a backtrace.
+    pub fn downgrade(this: &Self) -> AuditedWeak<T> {
+        let origin = Arc::new(Backtrace::force_capture());
+        let mut origins = lock(&this.origins);
+        origins.retain(|origin| origin.strong_count() > 0);
+        origins.push(Arc::downgrade(&origin));
+        AuditedWeak {
+            weak: Arc::downgrade(&this.arc),
+            origins: Arc::clone(&this.origins),
+            origin,
+        }
+    }
+
+    /// The backtraces of the weak handles to the current allocation that are
+    /// still alive, in order of creation.
+    pub fn live_origins(this: &Self) -> Vec<Arc<Backtrace>> {
+        lock(&this.origins)
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect()
+    }
+
+    /// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), and forgets the
+    /// backtraces of the orphaned handles.
+    ///
+    /// Returns None if the strong count was greater than 1 or a concurrent
+    /// weak upgrade won the race.
+    pub fn get_mut_drop_weak(&mut self) -> Option<&mut T> {
+        match get_mut_drop_weak_access(&mut self.arc) {
+            Access::Unchanged(value) | Access::Replaced(value) => {
+                lock(&self.origins).clear();
+                Some(value)
+            }
+            Access::Shared(_) => None,
+        }
+    }
+}
+
+impl<T> AuditedWeak<T> {
+    /// Attempts to upgrade to a strong pointer.
+    pub fn upgrade(&self) -> Option<AuditedArc<T>> {
+        Some(AuditedArc {
+            arc: self.weak.upgrade()?,
+            origins: Arc::clone(&self.origins),
+        })
+    }
+
+    /// The backtrace captured when this handle, or the one it was cloned
+    /// from, was created.
+    pub fn origin(&self) -> &Backtrace {
+        &self.origin
+    }
+}
+
+fn lock(origins: &Origins) -> MutexGuard<'_, Vec<Weak<Backtrace>>> {
+    origins.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl<T> Clone for AuditedArc<T> {
+    fn clone(&self) -> Self {
+        Self {
+            arc: Arc::clone(&self.arc),
+            origins: Arc::clone(&self.origins),
+        }
+    }
+}
+
+impl<T> Clone for AuditedWeak<T> {
+    fn clone(&self) -> Self {
+        Self {
+            weak: Weak::clone(&self.weak),
+            origins: Arc::clone(&self.origins),
+            origin: Arc::clone(&self.origin),
+        }
+    }
+}
+
+impl<T> Deref for AuditedArc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.arc
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for AuditedArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> fmt::Debug for AuditedWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(AuditedWeak)")
+    }
+}
@@ -0,0 +1,78 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::{ReplacementCache, SharedPointer, cache::cached_detailed};
+
+/// What [`get_mut_drop_weak_batch`] did with one pointer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BatchOutcome {
+    /// The pointer was already exclusive and kept its allocation.
+    Exclusive,
+    /// The value was moved to a new allocation, orphaning its weak pointers.
+    Replaced,
+    /// The pointer is strongly shared, or a concurrent weak upgrade won the
+    /// race. It was left unchanged.
+    Shared,
+}
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak) for every pointer in
+/// `ptrs`, returning what happened to each, in order.
+///
+/// Afterwards, every pointer reported as [`BatchOutcome::Exclusive`] or
+/// [`BatchOutcome::Replaced`] can be mutated with `get_mut`. A replacement
+/// allocation that lost the race is reused for the next pointer instead of
+/// being freed.
+pub fn get_mut_drop_weak_batch<T, P: SharedPointer<T>>(ptrs: &mut [P]) -> Vec<BatchOutcome> {
+    let mut cache = ReplacementCache::new();
+    ptrs.iter_mut()
+        .map(|ptr| outcome(ptr, &mut cache))
+        .collect()
+}
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak) on each pointer in
+/// turn, returning the value of the first one that could be made exclusive,
+/// e.g. to reclaim any buffer from a free list that nobody else holds.
+///
+/// Pointers before it that are strongly shared are left unchanged. Returns
+/// None if there is no such pointer.
+pub fn first_exclusive<'a, T: 'a, P: SharedPointer<T> + 'a>(
+    ptrs: impl IntoIterator<Item = &'a mut P>,
+) -> Option<&'a mut T> {
+    let mut cache = ReplacementCache::new();
+    ptrs.into_iter()
+        .find_map(|ptr| cached_detailed(ptr, &mut cache).ok())
+}
+
+/// Makes `ptr` exclusive, reusing the allocation in `cache`, and reports
+/// what happened.
+pub(crate) fn outcome<T, P: SharedPointer<T>>(
+    ptr: &mut P,
+    cache: &mut ReplacementCache<T, P>,
+) -> BatchOutcome {
+    let original = P::as_ptr(ptr);
+    match cached_detailed(ptr, cache) {
+        // The replacement is allocated while the original is still alive, so
+        // the addresses can't coincide.
+        Ok(value) if core::ptr::eq(value, original) => BatchOutcome::Exclusive,
+        Ok(_) => BatchOutcome::Replaced,
+        Err(_) => BatchOutcome::Shared,
+    }
+}
+
+/// Like [`get_mut_drop_weak_batch`] for the values of `map`, returning the
+/// keys whose values are still shared, so that the rest can be mutated with
+/// `get_mut`.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn make_unique_all_drop_weak<K: Eq + Hash + Clone, T, P: SharedPointer<T>, S: BuildHasher>(
+    map: &mut HashMap<K, P, S>,
+) -> Vec<K> {
+    let mut cache = ReplacementCache::new();
+    map.iter_mut()
+        .filter_map(|(key, ptr)| cached_detailed(ptr, &mut cache).err().map(|_| key.clone()))
+        .collect()
+}
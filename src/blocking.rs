@@ -0,0 +1,121 @@
+use core::hint;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{GetMutError, SharedPointer, Timeout, get_mut_drop_weak_detailed};
+
+/// The longest the blocking functions park the thread between two polls of
+/// the reference counts, and so the most latency they add after the last
+/// other strong pointer is dropped.
+pub const MAX_POLL_INTERVAL: Duration =
+    Duration::from_micros(1 << (Backoff::PARK_LIMIT - Backoff::YIELD_LIMIT));
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but if other strong
+/// pointers exist, waits up to `timeout` for them to be dropped.
+///
+/// Strong pointers don't announce when they are dropped, so this polls the
+/// counts, backing off from spinning to yielding to parking the thread for
+/// increasingly long intervals, up to [`MAX_POLL_INTERVAL`]. Waiting out
+/// short-lived readers therefore neither burns a core nor adds much latency.
+/// A reader can cut the wait short by calling
+/// [`Thread::unpark`](std::thread::Thread::unpark) on the waiting thread after
+/// dropping its pointer. To wait for a notification instead of polling, have
+/// the readers drop their pointers through a `ReleaseEvent` (with the
+/// `event-listener` feature).
+///
+/// Returns Err(&mut Arc<T>) if the pointer is still shared when the timeout
+/// expires.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_blocking<T, P: SharedPointer<T>>(
+    ptr: &mut P,
+    timeout: Duration,
+) -> Result<&mut T, &mut P> {
+    get_mut_drop_weak_until(ptr, Instant::now().checked_add(timeout))
+}
+
+/// Waits until `ptr` can be made exclusive, orphaning any weak pointers, and
+/// then runs `f` on the value.
+///
+/// This is [`get_mut_drop_weak_blocking`] with an absolute deadline, taking a
+/// closure so that the caller doesn't have to thread the returned reference
+/// through its own control flow. The thread waits the same way, polling the
+/// counts and parking for up to [`MAX_POLL_INTERVAL`] in between.
+///
+/// Returns Err(Timeout) without calling `f` if the pointer is still shared at
+/// `deadline`.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn with_exclusive<T, P: SharedPointer<T>, R>(
+    ptr: &mut P,
+    deadline: Instant,
+    f: impl FnOnce(&mut T) -> R,
+) -> Result<R, Timeout> {
+    match get_mut_drop_weak_until(ptr, Some(deadline)) {
+        Ok(value) => Ok(f(value)),
+        Err(_) => Err(Timeout),
+    }
+}
+
+/// [`get_mut_drop_weak_blocking`] with an absolute deadline, where `None`
+/// waits forever.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub(crate) fn get_mut_drop_weak_until<T, P: SharedPointer<T>>(
+    ptr: &mut P,
+    deadline: Option<Instant>,
+) -> Result<&mut T, &mut P> {
+    let mut ptr = ptr;
+    let mut backoff = Backoff::new();
+    loop {
+        match get_mut_drop_weak_detailed(ptr) {
+            Ok(value) => return Ok(value),
+            // Other strong pointers may still be dropped.
+            Err(err) if err.is_transient() => {
+                ptr = err.into_inner();
+                if !backoff.wait(deadline) {
+                    return Err(ptr);
+                }
+            }
+            Err(err) => return Err(GetMutError::into_inner(err)),
+        }
+    }
+}
+
+/// Exponential backoff for polling the reference counts.
+pub(crate) struct Backoff {
+    step: u32,
+}
+
+impl Backoff {
+    const SPIN_LIMIT: u32 = 6;
+    const YIELD_LIMIT: u32 = 10;
+    const PARK_LIMIT: u32 = 20;
+
+    pub(crate) fn new() -> Self {
+        Backoff { step: 0 }
+    }
+
+    /// Waits for the next polling interval, or returns false if `deadline`
+    /// has passed.
+    pub(crate) fn wait(&mut self, deadline: Option<Instant>) -> bool {
+        let now = Instant::now();
+        if deadline.is_some_and(|deadline| now >= deadline) {
+            return false;
+        }
+        if self.step < Self::SPIN_LIMIT {
+            for _ in 0..1 << self.step {
+                hint::spin_loop();
+            }
+        } else if self.step < Self::YIELD_LIMIT {
+            thread::yield_now();
+        } else {
+            let pause =
+                Duration::from_micros(1 << (self.step - Self::YIELD_LIMIT)).min(MAX_POLL_INTERVAL);
+            let pause = match deadline {
+                Some(deadline) => pause.min(deadline - now),
+                None => pause,
+            };
+            thread::park_timeout(pause);
+        }
+        self.step = (self.step + 1).min(Self::PARK_LIMIT);
+        true
+    }
+}
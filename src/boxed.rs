@@ -0,0 +1,20 @@
+use alloc::boxed::Box;
+
+use crate::{SharedPointer, get_mut_drop_weak};
+
+/// [`get_mut_drop_weak`] for pointers to a `Box<T>`, returning a reference to
+/// the boxed value.
+///
+/// Replacing the pointer moves its payload, which for a large `T` means
+/// copying the whole value across the stack and into the new allocation.
+/// Storing it as `Arc<Box<T>>` instead keeps the value where it is and only
+/// moves the box pointer. This also works for unsized values such as
+/// `Box<dyn Trait>` or `Box<[T]>`.
+///
+/// Returns Err(&mut Arc<Box<T>>) if the strong count was greater than 1.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_boxed<T: ?Sized, P: SharedPointer<Box<T>>>(
+    ptr: &mut P,
+) -> Result<&mut T, &mut P> {
+    get_mut_drop_weak(ptr).map(|boxed| &mut **boxed)
+}
@@ -0,0 +1,80 @@
+use core::{fmt, marker::PhantomData};
+
+use crate::pointer::sealed::Sealed;
+use crate::{GetMutError, SharedPointer, detailed_slow, is_exclusive};
+
+/// A replacement allocation kept by [`get_mut_drop_weak_cached`] after it
+/// lost the race to a weak upgrade, for the next attempt to reuse.
+///
+/// The allocation isn't tied to a particular pointer, so one cache can serve
+/// any number of pointers of type `P`. With the `nightly-allocator` feature,
+/// an allocation is only reused for pointers in a stateless allocator such as
+/// `Global`; for others, it is freed and the replacement is allocated in the
+/// pointer's own allocator.
+pub struct ReplacementCache<T, P: SharedPointer<T>> {
+    uninit: Option<<P as Sealed<T>>::Uninit>,
+    _marker: PhantomData<fn() -> (T, P)>,
+}
+
+impl<T, P: SharedPointer<T>> ReplacementCache<T, P> {
+    /// Creates an empty cache.
+    pub const fn new() -> Self {
+        Self {
+            uninit: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Whether the cache holds an allocation.
+    pub fn is_allocated(&self) -> bool {
+        self.uninit.is_some()
+    }
+
+    /// Frees the cached allocation, if any.
+    pub fn clear(&mut self) {
+        self.uninit = None;
+    }
+}
+
+impl<T, P: SharedPointer<T>> Default for ReplacementCache<T, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, P: SharedPointer<T>> fmt::Debug for ReplacementCache<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplacementCache")
+            .field("allocated", &self.is_allocated())
+            .finish()
+    }
+}
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but takes the
+/// replacement allocation from `cache` if it holds one, and leaves it there
+/// if a concurrent weak upgrade wins the race, so that an immediate retry
+/// doesn't allocate again.
+///
+/// Returns Err(&mut Arc<T>) if the strong count was greater than 1 or a
+/// concurrent weak upgrade won the race.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_cached<'a, T, P: SharedPointer<T>>(
+    ptr: &'a mut P,
+    cache: &mut ReplacementCache<T, P>,
+) -> Result<&'a mut T, &'a mut P> {
+    cached_detailed(ptr, cache).map_err(GetMutError::into_inner)
+}
+
+/// [`get_mut_drop_weak_cached`] with the reason for failure.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub(crate) fn cached_detailed<'a, T, P: SharedPointer<T>>(
+    ptr: &'a mut P,
+    cache: &mut ReplacementCache<T, P>,
+) -> Result<&'a mut T, GetMutError<'a, P>> {
+    if is_exclusive(ptr) {
+        // SAFETY: `is_exclusive` just confirmed there are no other pointers,
+        // and none can be created while `ptr` is mutably borrowed.
+        return Ok(unsafe { P::get_mut_unchecked(ptr) });
+    }
+    detailed_slow(ptr, &mut cache.uninit)
+}
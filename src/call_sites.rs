@@ -0,0 +1,63 @@
+use alloc::vec::Vec;
+use core::panic::Location;
+use std::sync::{Mutex, PoisonError};
+
+/// How many call sites [`orphaning_call_sites`] remembers. Older ones are
+/// overwritten.
+pub const CALL_SITE_CAPACITY: usize = 64;
+
+struct Ring {
+    sites: [Option<&'static Location<'static>>; CALL_SITE_CAPACITY],
+    /// Where the next call site goes.
+    next: usize,
+}
+
+static RING: Mutex<Ring> = Mutex::new(Ring {
+    sites: [None; CALL_SITE_CAPACITY],
+    next: 0,
+});
+
+pub(crate) fn record(location: &'static Location<'static>) {
+    let mut ring = RING.lock().unwrap_or_else(PoisonError::into_inner);
+    let next = ring.next;
+    ring.sites[next] = Some(location);
+    ring.next = (next + 1) % CALL_SITE_CAPACITY;
+}
+
+/// The source locations of the most recent calls that orphaned weak
+/// pointers by moving or copying a value to a new allocation, oldest first,
+/// to find out which call site keeps invalidating weak caches.
+///
+/// Locations are tracked with `#[track_caller]` through every free function
+/// that can orphan weak pointers, such as
+/// [`get_mut_drop_weak`](crate::get_mut_drop_weak),
+/// [`replace_drop_weak`](crate::replace_drop_weak),
+/// [`take_drop_weak`](crate::take_drop_weak),
+/// [`get_mut_drop_weak_pooled`](crate::get_mut_drop_weak_pooled) and
+/// [`get_mut_drop_weak_copy`](crate::get_mut_drop_weak_copy), through the
+/// [`ArcGetMutExt`](crate::ArcGetMutExt) methods, and through the methods of
+/// wrapper types that make their pointer exclusive, such as
+/// [`GuardedArc::get_mut_drop_weak`](crate::GuardedArc::get_mut_drop_weak).
+///
+/// `#[track_caller]` doesn't reach through closures or `async` code, so
+/// functions that replace from inside one, such as
+/// [`get_mut_drop_weak_batch`](crate::get_mut_drop_weak_batch), the future
+/// returned by [`get_mut_drop_weak_async`](crate::get_mut_drop_weak_async)
+/// and [`ArcCell::with_mut_drop_weak`](crate::ArcCell::with_mut_drop_weak),
+/// record a location in this crate instead.
+///
+/// Requires the `call-sites` feature.
+pub fn orphaning_call_sites() -> Vec<&'static Location<'static>> {
+    let ring = RING.lock().unwrap_or_else(PoisonError::into_inner);
+    let (newer, older) = ring.sites.split_at(ring.next);
+    older.iter().chain(newer).flatten().copied().collect()
+}
+
+/// Forgets all recorded call sites.
+///
+/// Requires the `call-sites` feature.
+pub fn clear_orphaning_call_sites() {
+    let mut ring = RING.lock().unwrap_or_else(PoisonError::into_inner);
+    ring.sites = [None; CALL_SITE_CAPACITY];
+    ring.next = 0;
+}
@@ -0,0 +1,106 @@
+use alloc::sync::Arc;
+use core::{cell::UnsafeCell, fmt};
+
+use crate::{get_mut_drop_weak, spin::SpinLock};
+
+/// An [`Arc`] in a cell, for callers that only have `&self`.
+///
+/// Every access takes a spin lock, held only for the duration of the access.
+/// [`ArcCell::with_mut_drop_weak`] gets exclusive access to the value like
+/// [`get_mut_drop_weak`](crate::get_mut_drop_weak), which fails while any
+/// `Arc` returned by [`ArcCell::load`] is still alive.
+pub struct ArcCell<T> {
+    lock: SpinLock,
+    arc: UnsafeCell<Arc<T>>,
+}
+
+impl<T> ArcCell<T> {
+    /// Allocates `value` in a new `Arc`.
+    pub fn new(value: T) -> Self {
+        Self::from_arc(Arc::new(value))
+    }
+
+    /// Wraps `arc`.
+    pub const fn from_arc(arc: Arc<T>) -> Self {
+        Self {
+            lock: SpinLock::new(),
+            arc: UnsafeCell::new(arc),
+        }
+    }
+
+    /// Unwraps the `Arc`.
+    pub fn into_arc(self) -> Arc<T> {
+        self.arc.into_inner()
+    }
+
+    /// The `Arc`, without locking.
+    pub fn get_mut(&mut self) -> &mut Arc<T> {
+        self.arc.get_mut()
+    }
+
+    /// Clones the `Arc`.
+    pub fn load(&self) -> Arc<T> {
+        self.with_arc(|arc| Arc::clone(arc))
+    }
+
+    /// Replaces the `Arc`, returning the old one.
+    pub fn swap(&self, arc: Arc<T>) -> Arc<T> {
+        self.with_arc(|old| core::mem::replace(old, arc))
+    }
+
+    /// Calls `f` with exclusive access to the value, orphaning any weak
+    /// pointers like [`get_mut_drop_weak`](crate::get_mut_drop_weak).
+    ///
+    /// Returns None without calling `f` if the strong count was greater than
+    /// 1, e.g. because of an outstanding [`ArcCell::load`], or a concurrent
+    /// weak upgrade won the race.
+    ///
+    /// The lock is held while `f` runs, as the value can't be reached by
+    /// anyone else until `f` is done with it. Every other access to the cell
+    /// spins in the meantime, so `f` should be short, and it must not call
+    /// [`ArcCell::load`], [`ArcCell::swap`] or `with_mut_drop_weak` on this
+    /// cell, which would spin forever. Formatting the cell with `Debug` is
+    /// fine and shows it as locked.
+    pub fn with_mut_drop_weak<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        self.with_arc(|arc| get_mut_drop_weak(arc).ok().map(f))
+    }
+
+    fn with_arc<R>(&self, f: impl FnOnce(&mut Arc<T>) -> R) -> R {
+        let _locked = self.lock.lock();
+        // SAFETY: The lock is held.
+        f(unsafe { &mut *self.arc.get() })
+    }
+}
+
+// SAFETY: The `Arc` is only accessed under the lock, so sharing the cell is
+// like sending the `Arc` to the thread that holds it.
+unsafe impl<T: Send + Sync> Sync for ArcCell<T> {}
+
+impl<T> From<Arc<T>> for ArcCell<T> {
+    fn from(arc: Arc<T>) -> Self {
+        ArcCell::from_arc(arc)
+    }
+}
+
+impl<T: Default> Default for ArcCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArcCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Don't wait for the lock, which may be held by the caller.
+        let loaded = self.lock.try_lock().map(|_locked| {
+            // SAFETY: The lock is held.
+            Arc::clone(unsafe { &*self.arc.get() })
+        });
+        match loaded {
+            Some(arc) => f.debug_tuple("ArcCell").field(&arc).finish(),
+            None => f
+                .debug_tuple("ArcCell")
+                .field(&format_args!("<locked>"))
+                .finish(),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{SharedPointer, fallback::insert, get_mut_drop_weak, instrument, replace_drop_weak};
+
+/// The result of [`get_mut_or_clone_out`].
+#[derive(Debug)]
+pub enum MutOrClone<'a, T, P> {
+    /// Exclusive access to the value behind the pointer.
+    Mut(&'a mut T),
+    /// The pointer is strongly shared. Holds a copy of the value to work on
+    /// instead.
+    Cloned(CloneOut<'a, T, P>),
+}
+
+/// A copy of the value behind a strongly shared pointer, from
+/// [`get_mut_or_clone_out`], that can be published back to the pointer once
+/// the other strong pointers are gone.
+///
+/// Unlike [`make_mut_drop_weak`](crate::make_mut_drop_weak), the pointer is
+/// left as it is until then, so readers sharing it keep seeing the same value.
+pub struct CloneOut<'a, T, P> {
+    value: T,
+    ptr: &'a mut P,
+}
+
+impl<'a, T, P: SharedPointer<T>> CloneOut<'a, T, P> {
+    /// Replaces the value behind the pointer with the copy, as
+    /// [`replace_drop_weak`] does, and returns the old value.
+    ///
+    /// Hands the copy back if the pointer is still strongly shared, or a
+    /// concurrent weak upgrade won the race.
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    pub fn publish(self) -> Result<T, Self> {
+        let Self { value, ptr } = self;
+        replace_drop_weak(ptr, value).map_err(|value| Self { value, ptr })
+    }
+
+    /// Points the pointer at a new allocation holding the copy, leaving the
+    /// other strong pointers with the old value, and returns the new value.
+    pub fn publish_detached(self) -> &'a mut T {
+        insert(self.ptr, self.value)
+    }
+
+    /// Discards the pointer and returns the copy.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// The pointer the copy was made from.
+    pub fn ptr(&self) -> &P {
+        self.ptr
+    }
+}
+
+impl<T, P> Deref for CloneOut<'_, T, P> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, P> DerefMut for CloneOut<'_, T, P> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: fmt::Debug, P> fmt::Debug for CloneOut<'_, T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CloneOut")
+            .field("value", &self.value)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but if the pointer
+/// is strongly shared (or a concurrent weak upgrade won the race), returns a
+/// [`CloneOut`] copy of the value to update and publish later.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_or_clone_out<T: Clone, P: SharedPointer<T>>(ptr: &mut P) -> MutOrClone<'_, T, P> {
+    match get_mut_drop_weak(ptr) {
+        Ok(value) => MutOrClone::Mut(value),
+        Err(ptr) => {
+            instrument::cloned();
+            MutOrClone::Cloned(CloneOut {
+                value: T::clone(ptr),
+                ptr,
+            })
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{SharedPointer, instrument, is_exclusive};
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but builds the
+/// replacement by cloning the value instead of moving it out.
+///
+/// This costs a clone whenever weak pointers have to be orphaned, but it is
+/// implemented entirely in safe code and can't lose a race against a
+/// concurrent weak upgrade: a weak pointer upgraded in the meantime simply
+/// keeps the original allocation.
+///
+/// Returns Err(&mut Arc<T>) if the strong count was greater than 1.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn clone_replace_drop_weak<T: Clone, P: SharedPointer<T>>(
+    ptr: &mut P,
+) -> Result<&mut T, &mut P> {
+    if is_exclusive(ptr) {
+        // SAFETY: `is_exclusive` just confirmed there are no other pointers,
+        // and none can be created while `ptr` is mutably borrowed.
+        return Ok(unsafe { P::get_mut_unchecked(ptr) });
+    }
+    let strong = P::strong_count(ptr);
+    if strong > 1 {
+        instrument::strongly_shared::<T>(strong, P::weak_count(ptr));
+        return Err(ptr);
+    }
+    // Strong = 1, Weak > 0. Replace with an unshared clone.
+    instrument::cloned();
+    let value = T::clone(ptr);
+    *ptr = P::new_like(ptr, value);
+    instrument::orphaned();
+    Ok(P::get_mut(ptr).expect("fresh allocation is unique"))
+}
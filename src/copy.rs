@@ -0,0 +1,30 @@
+use crate::{SharedPointer, instrument, is_exclusive};
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but for `Copy`
+/// payloads, which are copied into the replacement instead of moved out.
+///
+/// The old pointer is only released after the copy has been made, so there is
+/// no `try_unwrap` and no race to lose: a weak pointer upgraded in the
+/// meantime keeps the original allocation, and the caller still gets an
+/// exclusive reference to the new one. Once the replacement is allocated,
+/// this can't fail.
+///
+/// Returns Err(&mut Arc<T>) if the strong count was greater than 1.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_copy<T: Copy, P: SharedPointer<T>>(ptr: &mut P) -> Result<&mut T, &mut P> {
+    if is_exclusive(ptr) {
+        // SAFETY: `is_exclusive` just confirmed there are no other pointers,
+        // and none can be created while `ptr` is mutably borrowed.
+        return Ok(unsafe { P::get_mut_unchecked(ptr) });
+    }
+    let strong = P::strong_count(ptr);
+    if strong > 1 {
+        instrument::strongly_shared::<T>(strong, P::weak_count(ptr));
+        return Err(ptr);
+    }
+    // Strong = 1, Weak > 0. Replace with an unshared copy.
+    *ptr = P::new_like(ptr, **ptr);
+    instrument::cloned();
+    instrument::orphaned();
+    Ok(P::get_mut(ptr).expect("fresh allocation is unique"))
+}
@@ -0,0 +1,66 @@
+use core::fmt;
+
+use crate::{Exclusivity, SharedPointer};
+
+/// A snapshot of a pointer's counts, as returned by [`explain`], meant for
+/// logging when exclusive access unexpectedly fails.
+///
+/// Its [`Display`](fmt::Display) implementation gives a human-readable
+/// explanation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Diagnosis {
+    /// The strong count.
+    pub strong: usize,
+    /// The weak count.
+    pub weak: usize,
+}
+
+impl Diagnosis {
+    /// Whether there are weak pointers.
+    pub fn has_weak(&self) -> bool {
+        self.weak > 0
+    }
+
+    /// Whether [`get_mut_drop_weak`](crate::get_mut_drop_weak) would have to
+    /// replace the allocation.
+    pub fn needs_replacement(&self) -> bool {
+        matches!(self.exclusivity(), Exclusivity::WeakOnly { .. })
+    }
+
+    /// The same snapshot, classified.
+    pub fn exclusivity(&self) -> Exclusivity {
+        match (self.strong, self.weak) {
+            (strong @ 2.., _) => Exclusivity::Shared { strong },
+            (_, 0) => Exclusivity::Unique,
+            (_, weak) => Exclusivity::WeakOnly { weak },
+        }
+    }
+}
+
+impl fmt::Display for Diagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "strong = {}, weak = {}: ", self.strong, self.weak)?;
+        match self.exclusivity() {
+            Exclusivity::Unique => f.write_str("exclusive, no replacement needed"),
+            Exclusivity::WeakOnly { weak } => write!(
+                f,
+                "only weakly shared, replacement needed, which would orphan {weak} weak pointer(s)"
+            ),
+            Exclusivity::Shared { strong } => write!(
+                f,
+                "strongly shared by {strong} pointers, exclusive access impossible until {} other strong pointer(s) are dropped",
+                strong - 1
+            ),
+        }
+    }
+}
+
+/// Takes a [`Diagnosis`] of `ptr`.
+///
+/// Like [`exclusivity`](crate::exclusivity), this is only a snapshot.
+pub fn explain<T, P: SharedPointer<T>>(ptr: &P) -> Diagnosis {
+    Diagnosis {
+        strong: P::strong_count(ptr),
+        weak: P::weak_count(ptr),
+    }
+}
@@ -0,0 +1,72 @@
+use alloc::sync::Arc;
+use core::ffi::CStr;
+
+use crate::{instrument, pointer::arc_get_mut_unchecked};
+
+/// Unsized payloads that [`get_mut_drop_weak_unsized`] can rebuild into a
+/// fresh allocation by copying their bytes.
+///
+/// This trait is sealed. It is implemented for [`str`] and [`CStr`], and with
+/// the `std` feature for [`OsStr`](std::ffi::OsStr) and
+/// [`Path`](std::path::Path).
+pub trait Rebuild: sealed::Sealed {}
+
+mod sealed {
+    use alloc::sync::Arc;
+
+    pub trait Sealed {
+        /// Copies `this` into a new, unshared allocation.
+        fn rebuild(this: &Arc<Self>) -> Arc<Self>;
+    }
+}
+
+macro_rules! impl_rebuild {
+    ($ty:ty) => {
+        impl Rebuild for $ty {}
+
+        impl sealed::Sealed for $ty {
+            fn rebuild(this: &Arc<Self>) -> Arc<Self> {
+                Arc::from(&**this)
+            }
+        }
+    };
+}
+
+impl_rebuild!(str);
+impl_rebuild!(CStr);
+#[cfg(feature = "std")]
+impl_rebuild!(std::ffi::OsStr);
+#[cfg(feature = "std")]
+impl_rebuild!(std::path::Path);
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but for unsized
+/// payloads such as `Arc<str>` or `Arc<Path>`.
+///
+/// An unsized value can't be moved, so when only weak pointers block exclusive
+/// access, the allocation is rebuilt by copying the value and the original is
+/// dropped. This copy is always safe, so unlike `get_mut_drop_weak` it never
+/// fails because of a concurrent upgrade: a weak pointer upgraded in the
+/// meantime simply keeps the original allocation.
+///
+/// Returns Err(&mut Arc<U>) if the strong count was greater than 1.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_unsized<U: ?Sized + Rebuild>(
+    arc: &mut Arc<U>,
+) -> Result<&mut U, &mut Arc<U>> {
+    if Arc::get_mut(arc).is_some() {
+        instrument::fast_path();
+    } else {
+        let strong = Arc::strong_count(arc);
+        if strong > 1 {
+            instrument::strongly_shared::<U>(strong, Arc::weak_count(arc));
+            return Err(arc);
+        }
+        // Strong = 1, Weak > 0. Replace with an unshared copy.
+        *arc = sealed::Sealed::rebuild(arc);
+        instrument::cloned();
+        instrument::orphaned();
+    }
+    // SAFETY: Either `Arc::get_mut` succeeded, or `arc` was just replaced by a
+    // fresh allocation that nothing else can reach.
+    Ok(unsafe { arc_get_mut_unchecked(arc) })
+}
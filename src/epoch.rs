@@ -0,0 +1,158 @@
+use alloc::sync::{Arc, Weak};
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    ops::Deref,
+    sync::atomic::{AtomicUsize, Ordering, fence},
+};
+
+struct Inner<T> {
+    epoch: AtomicUsize,
+    // Upgraders that lose to an invalidation still briefly hold (and drop) a
+    // strong pointer while the owner has `&mut T`. The cell lets those
+    // references to `Inner` coexist with it.
+    value: UnsafeCell<T>,
+}
+
+/// An [`Arc`] whose weak handles ([`EpochWeak`]) are invalidated by bumping
+/// a generation number instead of replacing the allocation.
+///
+/// [`get_mut_drop_weak`](crate::get_mut_drop_weak) pays for an allocation and
+/// a move of `T` to orphan weak pointers. Here,
+/// [`EpochArc::get_mut_drop_weak`] invalidates all outstanding `EpochWeak`s
+/// with a single atomic increment: each handle remembers the generation it was
+/// created in, and [`EpochWeak::upgrade`] fails once that has changed. The
+/// value stays where it is, so its address is stable.
+///
+/// The allocation itself is freed only once the last `EpochWeak` is dropped,
+/// as with plain weak pointers. After `usize::MAX` invalidations the
+/// generation wraps around, and handles from `usize::MAX + 1` generations ago
+/// would work again.
+pub struct EpochArc<T> {
+    inner: Arc<Inner<T>>,
+}
+
+/// A weak handle to an [`EpochArc`], valid until the next invalidation.
+pub struct EpochWeak<T> {
+    inner: Weak<Inner<T>>,
+    epoch: usize,
+}
+
+impl<T> EpochArc<T> {
+    /// Allocates `value` in generation 0.
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                epoch: AtomicUsize::new(0),
+                value: UnsafeCell::new(value),
+            }),
+        }
+    }
+
+    /// Creates a weak handle for the current generation.
+    pub fn downgrade(this: &Self) -> EpochWeak<T> {
+        EpochWeak {
+            inner: Arc::downgrade(&this.inner),
+            epoch: this.inner.epoch.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Whether both pointers point to the same allocation.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.inner, &other.inner)
+    }
+
+    /// Returns a mutable reference to the value, invalidating all weak
+    /// handles if there are any. The value is not moved.
+    ///
+    /// Returns None if there are other strong pointers, including one just
+    /// upgraded from a weak handle. The weak handles are invalidated either
+    /// way once this gets past the strong count check.
+    pub fn get_mut_drop_weak(&mut self) -> Option<&mut T> {
+        if Arc::get_mut(&mut self.inner).is_none() {
+            if Arc::strong_count(&self.inner) > 1 {
+                return None;
+            }
+            // Strong = 1, Weak > 0. Invalidate the weak handles, then make sure
+            // none of them was upgraded before it could notice. Together with
+            // the fence in `EpochWeak::upgrade`, either the count below sees
+            // the upgrade, or the upgrader sees the new generation.
+            self.inner.epoch.fetch_add(1, Ordering::Relaxed);
+            fence(Ordering::SeqCst);
+            if Arc::strong_count(&self.inner) > 1 {
+                return None;
+            }
+            // Synchronize with the drops of other strong pointers.
+            fence(Ordering::Acquire);
+        }
+        // SAFETY: We hold the only strong pointer. Weak handles upgraded from
+        // now on see the new generation and never access `value`.
+        Some(unsafe { &mut *self.inner.value.get() })
+    }
+}
+
+impl<T> EpochWeak<T> {
+    /// Attempts to upgrade to a strong pointer. Fails if the value was
+    /// dropped, or if the handle was invalidated by
+    /// [`EpochArc::get_mut_drop_weak`].
+    pub fn upgrade(&self) -> Option<EpochArc<T>> {
+        let inner = self.inner.upgrade()?;
+        fence(Ordering::SeqCst);
+        // The owner may already hold `&mut` to the value, so don't access it
+        // until the generation is known to be current.
+        if inner.epoch.load(Ordering::Relaxed) == self.epoch {
+            Some(EpochArc { inner })
+        } else {
+            None
+        }
+    }
+}
+
+// SAFETY: `value` is accessed as through an `Arc<T>`: shared by
+// `Deref`, and mutably only with the sole strong pointer.
+unsafe impl<T: Send + Sync> Send for EpochArc<T> {}
+// SAFETY: As above.
+unsafe impl<T: Send + Sync> Sync for EpochArc<T> {}
+// SAFETY: An `EpochWeak` only gives access to the value by upgrading.
+unsafe impl<T: Send + Sync> Send for EpochWeak<T> {}
+// SAFETY: As above.
+unsafe impl<T: Send + Sync> Sync for EpochWeak<T> {}
+
+impl<T> Clone for EpochArc<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> Clone for EpochWeak<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Weak::clone(&self.inner),
+            epoch: self.epoch,
+        }
+    }
+}
+
+impl<T> Deref for EpochArc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: Mutable access requires `&mut self` on the only strong
+        // pointer, so it can't overlap with this borrow.
+        unsafe { &*self.inner.value.get() }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for EpochArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> fmt::Debug for EpochWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(EpochWeak)")
+    }
+}
@@ -0,0 +1,203 @@
+use core::{error, fmt};
+
+/// Why exclusive access couldn't be obtained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FailureReason {
+    /// The strong count was greater than 1.
+    StronglyShared,
+    /// The strong count was 1, but a concurrent weak upgrade won the race
+    /// before the value could be moved out.
+    LostRace,
+    /// The strong count was 1, but there were weak pointers, and the caller
+    /// asked for the allocation to be kept, e.g. with
+    /// [`get_mut_stable`](crate::get_mut_stable).
+    WouldReplace,
+}
+
+/// The error returned by
+/// [`get_mut_drop_weak_detailed`](crate::get_mut_drop_weak_detailed).
+///
+/// Like the Err variant of [`get_mut_drop_weak`](crate::get_mut_drop_weak),
+/// it hands back the pointer, along with the counts observed when the attempt
+/// failed.
+pub struct GetMutError<'a, P> {
+    ptr: &'a mut P,
+    strong: usize,
+    weak: usize,
+    reason: FailureReason,
+}
+
+impl<'a, P> GetMutError<'a, P> {
+    pub(crate) fn new(ptr: &'a mut P, strong: usize, weak: usize, reason: FailureReason) -> Self {
+        Self {
+            ptr,
+            strong,
+            weak,
+            reason,
+        }
+    }
+
+    /// The strong count observed when the attempt failed.
+    pub fn strong_count(&self) -> usize {
+        self.strong
+    }
+
+    /// The weak count observed when the attempt failed.
+    pub fn weak_count(&self) -> usize {
+        self.weak
+    }
+
+    /// Why the attempt failed.
+    pub fn reason(&self) -> FailureReason {
+        self.reason
+    }
+
+    /// Hands back the pointer.
+    pub fn into_inner(self) -> &'a mut P {
+        self.ptr
+    }
+
+    /// Whether the attempt may succeed once other strong pointers are dropped.
+    pub(crate) fn is_transient(&self) -> bool {
+        matches!(
+            self.reason,
+            FailureReason::StronglyShared | FailureReason::LostRace
+        )
+    }
+}
+
+impl<P> fmt::Debug for GetMutError<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GetMutError")
+            .field("strong", &self.strong)
+            .field("weak", &self.weak)
+            .field("reason", &self.reason)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P> fmt::Display for GetMutError<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.reason {
+            FailureReason::StronglyShared => f.write_str("value is strongly shared")?,
+            FailureReason::LostRace => {
+                f.write_str("lost the race against a concurrent weak upgrade")?
+            }
+            FailureReason::WouldReplace => {
+                f.write_str("weak pointers can't be dissociated without a new allocation")?
+            }
+        }
+        write!(f, " (strong = {}, weak = {})", self.strong, self.weak)
+    }
+}
+
+impl<P> error::Error for GetMutError<'_, P> {}
+
+/// The error returned by the `get_mut_all` method generated by
+/// [`GetMutAll`](crate::GetMutAll), naming the field that couldn't be made
+/// exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldBlocked {
+    field: &'static str,
+    reason: FailureReason,
+}
+
+impl FieldBlocked {
+    pub(crate) fn new(field: &'static str, reason: FailureReason) -> Self {
+        Self { field, reason }
+    }
+
+    /// The name of the field.
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    /// Why the field couldn't be made exclusive.
+    pub fn reason(&self) -> FailureReason {
+        self.reason
+    }
+}
+
+impl fmt::Display for FieldBlocked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "field `{}`: ", self.field)?;
+        match self.reason {
+            FailureReason::StronglyShared => f.write_str("value is strongly shared"),
+            FailureReason::LostRace => {
+                f.write_str("lost the race against a concurrent weak upgrade")
+            }
+            FailureReason::WouldReplace => {
+                f.write_str("weak pointers can't be dissociated without a new allocation")
+            }
+        }
+    }
+}
+
+impl error::Error for FieldBlocked {}
+
+/// Allocating a replacement pointer failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AllocFailed;
+
+impl fmt::Display for AllocFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to allocate the replacement")
+    }
+}
+
+impl error::Error for AllocFailed {}
+
+/// The deadline passed before the pointer could be made exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timeout;
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timed out waiting for exclusive access")
+    }
+}
+
+impl error::Error for Timeout {}
+
+/// The error returned by
+/// [`try_get_mut_drop_weak`](crate::try_get_mut_drop_weak). Either way, the
+/// pointer is handed back unchanged.
+pub enum TryGetMutError<'a, P> {
+    /// The strong count was greater than 1, or a concurrent weak upgrade won
+    /// the race, as told by the inner error's
+    /// [`reason`](GetMutError::reason).
+    Shared(GetMutError<'a, P>),
+    /// The replacement couldn't be allocated.
+    AllocFailed(&'a mut P),
+}
+
+impl<'a, P> TryGetMutError<'a, P> {
+    /// Hands back the pointer.
+    pub fn into_inner(self) -> &'a mut P {
+        match self {
+            TryGetMutError::Shared(err) => err.into_inner(),
+            TryGetMutError::AllocFailed(ptr) => ptr,
+        }
+    }
+}
+
+impl<P> fmt::Debug for TryGetMutError<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryGetMutError::Shared(err) => f.debug_tuple("Shared").field(err).finish(),
+            TryGetMutError::AllocFailed(_) => f.write_str("AllocFailed(..)"),
+        }
+    }
+}
+
+impl<P> fmt::Display for TryGetMutError<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryGetMutError::Shared(err) => fmt::Display::fmt(err, f),
+            TryGetMutError::AllocFailed(_) => fmt::Display::fmt(&AllocFailed, f),
+        }
+    }
+}
+
+impl<P> error::Error for TryGetMutError<'_, P> {}
@@ -0,0 +1,29 @@
+use crate::SharedPointer;
+
+/// How a pointer is shared, as reported by [`exclusivity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Exclusivity {
+    /// The only pointer, strong or weak. Exclusive access is free.
+    Unique,
+    /// The only strong pointer, with `weak` weak pointers. Exclusive access
+    /// requires a replacement, and so an allocation.
+    WeakOnly { weak: usize },
+    /// One of `strong` strong pointers. Exclusive access can't be obtained.
+    Shared { strong: usize },
+}
+
+/// Cheaply checks how `ptr` is shared, to decide whether to attempt
+/// [`get_mut_drop_weak`](crate::get_mut_drop_weak) at all.
+///
+/// This is only a snapshot: other threads may clone, drop or upgrade pointers
+/// right after it is taken.
+pub fn exclusivity<T, P: SharedPointer<T>>(ptr: &P) -> Exclusivity {
+    let strong = P::strong_count(ptr);
+    if strong > 1 {
+        return Exclusivity::Shared { strong };
+    }
+    match P::weak_count(ptr) {
+        0 => Exclusivity::Unique,
+        weak => Exclusivity::WeakOnly { weak },
+    }
+}
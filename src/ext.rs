@@ -0,0 +1,280 @@
+use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "std")]
+use crate::Timeout;
+use crate::{
+    Access, ArcMutGuard, Diagnosis, Exclusivity, GetMutDropWeak, GetMutError, NoSelfWeak,
+    ReplacementCache, SharedPointer, TryGetMutError, UpdateError,
+};
+
+/// Method-call syntax for the functions in this crate.
+///
+/// Implemented for every [`SharedPointer`], so `arc.get_mut_drop_weak()` is
+/// equivalent to `get_mut_drop_weak(&mut arc)`.
+pub trait ArcGetMutExt<T>: SharedPointer<T> {
+    /// See [`get_mut_drop_weak`](crate::get_mut_drop_weak).
+    fn get_mut_drop_weak(&mut self) -> Result<&mut T, &mut Self>;
+
+    /// See [`get_mut_drop_weak_access`](crate::get_mut_drop_weak_access).
+    fn get_mut_drop_weak_access(&mut self) -> Access<'_, T, Self>;
+
+    /// See [`get_mut_drop_weak_counted`](crate::get_mut_drop_weak_counted).
+    fn get_mut_drop_weak_counted(&mut self) -> Result<(&mut T, usize), &mut Self>;
+
+    /// See [`get_mut_drop_weak_with_old`](crate::get_mut_drop_weak_with_old).
+    fn get_mut_drop_weak_with_old(&mut self) -> Result<(&mut T, Option<*const T>), &mut Self>;
+
+    /// See [`get_mut_drop_weak_detailed`](crate::get_mut_drop_weak_detailed).
+    fn get_mut_drop_weak_detailed(&mut self) -> Result<&mut T, GetMutError<'_, Self>>;
+
+    /// See [`try_get_mut_drop_weak`](crate::try_get_mut_drop_weak).
+    fn try_get_mut_drop_weak(&mut self) -> Result<&mut T, TryGetMutError<'_, Self>>;
+
+    /// See [`get_mut_drop_weak_retry`](crate::get_mut_drop_weak_retry).
+    fn get_mut_drop_weak_retry(&mut self, attempts: usize) -> Result<&mut T, &mut Self>;
+
+    /// See [`get_mut_drop_weak_cached`](crate::get_mut_drop_weak_cached).
+    fn get_mut_drop_weak_cached(
+        &mut self,
+        cache: &mut ReplacementCache<T, Self>,
+    ) -> Result<&mut T, &mut Self>;
+
+    /// See [`get_mut_drop_weak_blocking`](crate::get_mut_drop_weak_blocking).
+    #[cfg(feature = "std")]
+    fn get_mut_drop_weak_blocking(&mut self, timeout: Duration) -> Result<&mut T, &mut Self>;
+
+    /// See [`with_exclusive`](crate::with_exclusive).
+    #[cfg(feature = "std")]
+    fn with_exclusive<R>(
+        &mut self,
+        deadline: Instant,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> Result<R, Timeout>;
+
+    /// See [`get_mut_drop_weak_async`](crate::get_mut_drop_weak_async).
+    fn get_mut_drop_weak_async(&mut self) -> GetMutDropWeak<'_, T, Self>;
+
+    /// See [`get_mut_drop_weak_map`](crate::get_mut_drop_weak_map).
+    fn get_mut_drop_weak_map<'a, U: ?Sized>(
+        &'a mut self,
+        proj: impl FnOnce(&'a mut T) -> &'a mut U,
+    ) -> Result<&'a mut U, &'a mut Self>
+    where
+        T: 'a;
+
+    /// See [`get_mut_drop_weak_guard`](crate::get_mut_drop_weak_guard).
+    fn get_mut_drop_weak_guard(&mut self) -> Result<ArcMutGuard<'_, T>, &mut Self>;
+
+    /// See [`get_mut_drop_weak_boxed`](crate::get_mut_drop_weak_boxed).
+    fn get_mut_drop_weak_boxed<U: ?Sized>(&mut self) -> Result<&mut U, &mut Self>
+    where
+        Self: SharedPointer<Box<U>>;
+
+    /// See [`get_mut_drop_weak_strict`](crate::get_mut_drop_weak_strict).
+    fn get_mut_drop_weak_strict(&mut self) -> Result<&mut T, &mut Self>
+    where
+        T: NoSelfWeak;
+
+    /// See [`get_mut_drop_weak_copy`](crate::get_mut_drop_weak_copy).
+    fn get_mut_drop_weak_copy(&mut self) -> Result<&mut T, &mut Self>
+    where
+        T: Copy;
+
+    /// See [`make_mut_drop_weak`](crate::make_mut_drop_weak).
+    fn make_mut_drop_weak(&mut self) -> &mut T
+    where
+        T: Clone;
+
+    /// See [`clone_replace_drop_weak`](crate::clone_replace_drop_weak).
+    fn clone_replace_drop_weak(&mut self) -> Result<&mut T, &mut Self>
+    where
+        T: Clone;
+
+    /// See [`take_drop_weak`](crate::take_drop_weak).
+    fn take_drop_weak(&mut self) -> Option<T>
+    where
+        T: Default;
+
+    /// See [`replace_drop_weak`](crate::replace_drop_weak).
+    fn replace_drop_weak(&mut self, new: T) -> Result<T, T>;
+
+    /// See [`into_box_drop_weak`](crate::into_box_drop_weak).
+    fn into_box_drop_weak(self) -> Result<Box<T>, Self>;
+
+    /// See [`exclusivity`](crate::exclusivity).
+    fn exclusivity(&self) -> Exclusivity;
+
+    /// See [`explain`](crate::explain).
+    fn explain(&self) -> Diagnosis;
+
+    /// See [`try_update`](crate::try_update).
+    fn try_update<E>(
+        &mut self,
+        f: impl FnOnce(T) -> Result<T, (T, E)>,
+    ) -> Result<(), UpdateError<E>>;
+
+    /// See [`update_with`](crate::update_with).
+    fn update_with(&mut self, f: impl FnOnce(T) -> T) -> bool;
+}
+
+impl<T, P: SharedPointer<T>> ArcGetMutExt<T> for P {
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn get_mut_drop_weak(&mut self) -> Result<&mut T, &mut Self> {
+        crate::get_mut_drop_weak(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn get_mut_drop_weak_access(&mut self) -> Access<'_, T, Self> {
+        crate::get_mut_drop_weak_access(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn get_mut_drop_weak_counted(&mut self) -> Result<(&mut T, usize), &mut Self> {
+        crate::get_mut_drop_weak_counted(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn get_mut_drop_weak_with_old(&mut self) -> Result<(&mut T, Option<*const T>), &mut Self> {
+        crate::get_mut_drop_weak_with_old(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn get_mut_drop_weak_detailed(&mut self) -> Result<&mut T, GetMutError<'_, Self>> {
+        crate::get_mut_drop_weak_detailed(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn try_get_mut_drop_weak(&mut self) -> Result<&mut T, TryGetMutError<'_, Self>> {
+        crate::try_get_mut_drop_weak(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn get_mut_drop_weak_retry(&mut self, attempts: usize) -> Result<&mut T, &mut Self> {
+        crate::get_mut_drop_weak_retry(self, attempts)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn get_mut_drop_weak_cached(
+        &mut self,
+        cache: &mut ReplacementCache<T, Self>,
+    ) -> Result<&mut T, &mut Self> {
+        crate::get_mut_drop_weak_cached(self, cache)
+    }
+
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn get_mut_drop_weak_blocking(&mut self, timeout: Duration) -> Result<&mut T, &mut Self> {
+        crate::get_mut_drop_weak_blocking(self, timeout)
+    }
+
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn with_exclusive<R>(
+        &mut self,
+        deadline: Instant,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> Result<R, Timeout> {
+        crate::with_exclusive(self, deadline, f)
+    }
+
+    fn get_mut_drop_weak_async(&mut self) -> GetMutDropWeak<'_, T, Self> {
+        crate::get_mut_drop_weak_async(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn get_mut_drop_weak_map<'a, U: ?Sized>(
+        &'a mut self,
+        proj: impl FnOnce(&'a mut T) -> &'a mut U,
+    ) -> Result<&'a mut U, &'a mut Self>
+    where
+        T: 'a,
+    {
+        crate::get_mut_drop_weak_map(self, proj)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn get_mut_drop_weak_guard(&mut self) -> Result<ArcMutGuard<'_, T>, &mut Self> {
+        crate::get_mut_drop_weak_guard(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn get_mut_drop_weak_boxed<U: ?Sized>(&mut self) -> Result<&mut U, &mut Self>
+    where
+        Self: SharedPointer<Box<U>>,
+    {
+        crate::get_mut_drop_weak_boxed(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn get_mut_drop_weak_strict(&mut self) -> Result<&mut T, &mut Self>
+    where
+        T: NoSelfWeak,
+    {
+        crate::get_mut_drop_weak_strict(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn get_mut_drop_weak_copy(&mut self) -> Result<&mut T, &mut Self>
+    where
+        T: Copy,
+    {
+        crate::get_mut_drop_weak_copy(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn make_mut_drop_weak(&mut self) -> &mut T
+    where
+        T: Clone,
+    {
+        crate::make_mut_drop_weak(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn clone_replace_drop_weak(&mut self) -> Result<&mut T, &mut Self>
+    where
+        T: Clone,
+    {
+        crate::clone_replace_drop_weak(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn take_drop_weak(&mut self) -> Option<T>
+    where
+        T: Default,
+    {
+        crate::take_drop_weak(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn replace_drop_weak(&mut self, new: T) -> Result<T, T> {
+        crate::replace_drop_weak(self, new)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn into_box_drop_weak(self) -> Result<Box<T>, Self> {
+        crate::into_box_drop_weak(self)
+    }
+
+    fn exclusivity(&self) -> Exclusivity {
+        crate::exclusivity(self)
+    }
+
+    fn explain(&self) -> Diagnosis {
+        crate::explain(self)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn try_update<E>(
+        &mut self,
+        f: impl FnOnce(T) -> Result<T, (T, E)>,
+    ) -> Result<(), UpdateError<E>> {
+        crate::try_update(self, f)
+    }
+
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    fn update_with(&mut self, f: impl FnOnce(T) -> T) -> bool {
+        crate::update_with(self, f)
+    }
+}
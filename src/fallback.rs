@@ -0,0 +1,69 @@
+use crate::{SharedPointer, instrument};
+
+/// Fallbacks for the result of [`get_mut_drop_weak`](crate::get_mut_drop_weak)
+/// and its variants, for when the pointer turned out to be strongly shared.
+///
+/// Each fallback points the caller's pointer at a new, unshared allocation,
+/// leaving the other strong pointers with the original value:
+///
+/// ```
+/// use std::sync::Arc;
+/// use get_mut_drop_weak::{DropWeakResultExt, get_mut_drop_weak};
+///
+/// let mut arc = Arc::new(vec![1]);
+/// let other = Arc::clone(&arc);
+/// get_mut_drop_weak(&mut arc).or_clone().push(2);
+/// assert_eq!(*arc, [1, 2]);
+/// assert_eq!(*other, [1]);
+/// ```
+pub trait DropWeakResultExt<'a, T> {
+    /// Falls back to cloning the value, like
+    /// [`make_mut_drop_weak`](crate::make_mut_drop_weak).
+    fn or_clone(self) -> &'a mut T
+    where
+        T: Clone;
+
+    /// Falls back to a default value.
+    fn or_default(self) -> &'a mut T
+    where
+        T: Default;
+
+    /// Falls back to the value returned by `f`.
+    fn or_insert_with(self, f: impl FnOnce() -> T) -> &'a mut T;
+}
+
+impl<'a, T, P: SharedPointer<T>> DropWeakResultExt<'a, T> for Result<&'a mut T, &'a mut P> {
+    fn or_clone(self) -> &'a mut T
+    where
+        T: Clone,
+    {
+        match self {
+            Ok(value) => value,
+            Err(ptr) => {
+                instrument::cloned();
+                insert(ptr, T::clone(ptr))
+            }
+        }
+    }
+
+    fn or_default(self) -> &'a mut T
+    where
+        T: Default,
+    {
+        self.or_insert_with(T::default)
+    }
+
+    fn or_insert_with(self, f: impl FnOnce() -> T) -> &'a mut T {
+        match self {
+            Ok(value) => value,
+            Err(ptr) => insert(ptr, f()),
+        }
+    }
+}
+
+/// Replaces `ptr` with a new allocation holding `value`.
+pub(crate) fn insert<T, P: SharedPointer<T>>(ptr: &mut P, value: T) -> &mut T {
+    *ptr = P::new_like(ptr, value);
+    // SAFETY: `ptr` was just replaced by a fresh allocation.
+    unsafe { P::get_mut_unchecked(ptr) }
+}
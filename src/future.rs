@@ -0,0 +1,101 @@
+use core::fmt;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use crate::{GetMutError, SharedPointer, get_mut_drop_weak_detailed};
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but returns a future
+/// that waits for other strong pointers to be dropped.
+///
+/// Strong pointers don't announce when they are dropped, and this future has
+/// no timer to check back later, so while the pointer is still shared it
+/// wakes itself right away, yielding to the executor between polls. That
+/// keeps the executor busy re-polling it: to back off instead, use
+/// [`get_mut_drop_weak_async_with`] with your runtime's sleep. If the readers
+/// can announce that they are done, prefer
+/// [`ReleaseEvent`](crate::ReleaseEvent), whose future only wakes when a
+/// pointer is released.
+///
+/// The future keeps waiting until the replacement succeeds. Combine it with a
+/// timer from your runtime to give up after a while.
+pub fn get_mut_drop_weak_async<T, P: SharedPointer<T>>(ptr: &mut P) -> GetMutDropWeak<'_, T, P> {
+    GetMutDropWeak {
+        ptr: Some(ptr),
+        _marker: PhantomData,
+    }
+}
+
+/// Like [`get_mut_drop_weak_async`], but while the pointer is still shared,
+/// waits on `sleep(delay)` before checking again, e.g. with
+/// `tokio::time::sleep` as `sleep`.
+///
+/// The delay starts at 50 microseconds and doubles up to a millisecond, so
+/// waiting out a reader neither keeps the executor busy nor adds much
+/// latency.
+pub async fn get_mut_drop_weak_async_with<
+    'a,
+    T: 'a,
+    P: SharedPointer<T>,
+    F: Future<Output = ()>,
+>(
+    ptr: &'a mut P,
+    mut sleep: impl FnMut(Duration) -> F,
+) -> Result<&'a mut T, &'a mut P> {
+    let mut ptr = ptr;
+    let mut delay = MIN_DELAY;
+    loop {
+        match get_mut_drop_weak_detailed(ptr) {
+            Ok(value) => return Ok(value),
+            // Other strong pointers may still be dropped.
+            Err(err) if err.is_transient() => {
+                ptr = err.into_inner();
+                sleep(delay).await;
+                delay = delay.saturating_mul(2).min(MAX_DELAY);
+            }
+            Err(err) => return Err(GetMutError::into_inner(err)),
+        }
+    }
+}
+
+/// The future returned by [`get_mut_drop_weak_async`].
+#[must_use = "futures do nothing unless polled"]
+pub struct GetMutDropWeak<'a, T, P> {
+    ptr: Option<&'a mut P>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T, P: fmt::Debug> fmt::Debug for GetMutDropWeak<'_, T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GetMutDropWeak")
+            .field("ptr", &self.ptr)
+            .finish()
+    }
+}
+
+impl<'a, T: 'a, P: SharedPointer<T>> Future for GetMutDropWeak<'a, T, P> {
+    type Output = Result<&'a mut T, &'a mut P>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let ptr = self.ptr.take().expect("polled after completion");
+        match get_mut_drop_weak_detailed(ptr) {
+            Ok(value) => Poll::Ready(Ok(value)),
+            // Other strong pointers may still be dropped.
+            Err(err) if err.is_transient() => {
+                self.ptr = Some(err.into_inner());
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            Err(err) => Poll::Ready(Err(GetMutError::into_inner(err))),
+        }
+    }
+}
+
+/// How long [`get_mut_drop_weak_async_with`] first sleeps for other strong
+/// pointers to be dropped.
+const MIN_DELAY: Duration = Duration::from_micros(50);
+
+/// The longest [`get_mut_drop_weak_async_with`] sleeps between checks.
+const MAX_DELAY: Duration = Duration::from_millis(1);
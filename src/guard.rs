@@ -0,0 +1,48 @@
+use core::ptr;
+
+/// Turns an unwind into an abort while alive.
+///
+/// Used around calls into code we don't control while a caller's pointer is
+/// moved out, where unwinding would expose the moved-out slot.
+pub(crate) struct AbortOnUnwind;
+
+impl AbortOnUnwind {
+    /// Disarms the guard.
+    pub(crate) fn disarm(self) {
+        core::mem::forget(self);
+    }
+}
+
+impl Drop for AbortOnUnwind {
+    fn drop(&mut self) {
+        // Only reached while unwinding; panicking again aborts the process.
+        panic!("get_mut_drop_weak: unwinding out of a critical section");
+    }
+}
+
+/// Moves `*slot` out, hands it to `f`, and writes back what `f` returns.
+///
+/// If `f` unwinds, `*slot` would be left moved out for its owner to drop
+/// again, so the process is aborted instead. This keeps a panic introduced
+/// by a future change, or by a user closure, from corrupting memory.
+//
+// # Aliasing
+// `slot` is converted into a single raw pointer that performs both the read
+// and the write. While `f` runs, it owns the value and nothing else refers to
+// `*slot`: the caller's unique borrow is suspended for the whole call, so the
+// raw pointer, derived from that borrow, stays valid under both Stacked and
+// Tree Borrows. No pointer-integer casts are involved.
+//
+// Checked with `MIRIFLAGS="-Zmiri-strict-provenance -Zmiri-tree-borrows"
+// cargo +nightly miri test`.
+pub(crate) fn moved_out<P, R>(slot: &mut P, f: impl FnOnce(P) -> (P, R)) -> R {
+    let slot = ptr::from_mut(slot);
+    let guard = AbortOnUnwind;
+    // SAFETY: `slot` comes from a unique borrow, so it is valid for reads and
+    // writes, and the value is written back before that borrow is used again.
+    // The guard covers exactly the span in which `*slot` is moved out.
+    let (next, result) = f(unsafe { slot.read() });
+    unsafe { slot.write(next) };
+    guard.disarm();
+    result
+}
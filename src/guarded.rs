@@ -0,0 +1,111 @@
+use alloc::sync::{Arc, Weak};
+use core::{fmt, ops::Deref};
+
+use crate::{get_mut_drop_weak, spin::SpinLock};
+
+/// An [`Arc`] whose weak handles ([`GuardedWeak`]) upgrade under a lock that
+/// [`GuardedArc::get_mut_drop_weak`] also takes.
+///
+/// With a plain `Arc`, a concurrent upgrade can win the race against the
+/// replacement after it has already allocated, and `get_mut_drop_weak` fails.
+/// Here, upgrades wait while the value is moved into the new allocation, so
+/// the replacement can only fail because of other strong pointers, which is
+/// checked before allocating.
+///
+/// The lock is a spin lock held only for the duration of an upgrade or a
+/// replacement.
+pub struct GuardedArc<T> {
+    arc: Arc<T>,
+    lock: Arc<SpinLock>,
+}
+
+/// A weak handle to a [`GuardedArc`].
+pub struct GuardedWeak<T> {
+    weak: Weak<T>,
+    lock: Arc<SpinLock>,
+}
+
+impl<T> GuardedArc<T> {
+    /// Allocates `value` in a new `Arc`.
+    pub fn new(value: T) -> Self {
+        Self {
+            arc: Arc::new(value),
+            lock: Arc::new(SpinLock::new()),
+        }
+    }
+
+    /// Creates a weak handle.
+    pub fn downgrade(this: &Self) -> GuardedWeak<T> {
+        GuardedWeak {
+            weak: Arc::downgrade(&this.arc),
+            lock: Arc::clone(&this.lock),
+        }
+    }
+
+    /// Whether both pointers point to the same allocation.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.arc, &other.arc)
+    }
+
+    /// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but can't lose
+    /// the race against a concurrent upgrade.
+    ///
+    /// Returns None if the strong count was greater than 1.
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    pub fn get_mut_drop_weak(&mut self) -> Option<&mut T> {
+        let _locked = self.lock.lock();
+        // With upgrades blocked, only other strong pointers can make this
+        // fail, and they are checked before allocating.
+        get_mut_drop_weak(&mut self.arc).ok()
+    }
+}
+
+impl<T> GuardedWeak<T> {
+    /// Attempts to upgrade to a strong pointer, waiting for a replacement in
+    /// progress to finish.
+    pub fn upgrade(&self) -> Option<GuardedArc<T>> {
+        let _locked = self.lock.lock();
+        Some(GuardedArc {
+            arc: self.weak.upgrade()?,
+            lock: Arc::clone(&self.lock),
+        })
+    }
+}
+
+impl<T> Clone for GuardedArc<T> {
+    fn clone(&self) -> Self {
+        Self {
+            arc: Arc::clone(&self.arc),
+            lock: Arc::clone(&self.lock),
+        }
+    }
+}
+
+impl<T> Clone for GuardedWeak<T> {
+    fn clone(&self) -> Self {
+        Self {
+            weak: Weak::clone(&self.weak),
+            lock: Arc::clone(&self.lock),
+        }
+    }
+}
+
+impl<T> Deref for GuardedArc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.arc
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for GuardedArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> fmt::Debug for GuardedWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(GuardedWeak)")
+    }
+}
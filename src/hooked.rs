@@ -0,0 +1,142 @@
+use alloc::{
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{fmt, ops::Deref};
+
+use crate::{Access, get_mut_drop_weak_access};
+
+type Hook = dyn Fn() + Send + Sync;
+
+/// An [`Arc`] whose weak handles ([`HookedWeak`]) carry a callback that is
+/// called when [`HookedArc::get_mut_drop_weak`] orphans them.
+///
+/// Where [`ObservableArc`](crate::ObservableArc) notifies its owner's
+/// subscribers, here each weak handle brings its own callback, e.g. to evict
+/// the cache entry holding it right away instead of finding it dead on the
+/// next upgrade. A callback is dropped without being called if all clones of
+/// its handle are dropped first.
+pub struct HookedArc<T> {
+    arc: Arc<T>,
+    hooks: Vec<Weak<Hook>>,
+}
+
+/// A weak handle to a [`HookedArc`] with a callback for its invalidation.
+pub struct HookedWeak<T> {
+    weak: Weak<T>,
+    hook: Arc<Hook>,
+}
+
+impl<T> HookedArc<T> {
+    /// Allocates `value` in a new `Arc`.
+    pub fn new(value: T) -> Self {
+        Self::from_arc(Arc::new(value))
+    }
+
+    /// Wraps `arc`. Weak pointers it already has are orphaned as usual, with
+    /// no callback.
+    pub fn from_arc(arc: Arc<T>) -> Self {
+        Self {
+            arc,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Unwraps the `Arc`, dropping the callbacks.
+    pub fn into_arc(this: Self) -> Arc<T> {
+        this.arc
+    }
+
+    /// The wrapped `Arc`, e.g. to clone it.
+    pub fn as_arc(this: &Self) -> &Arc<T> {
+        &this.arc
+    }
+
+    /// Creates a plain weak pointer, without a callback.
+    pub fn downgrade(this: &Self) -> Weak<T> {
+        Arc::downgrade(&this.arc)
+    }
+
+    /// Creates a weak handle that calls `on_invalidate` once it is orphaned.
+    pub fn downgrade_with(
+        this: &mut Self,
+        on_invalidate: impl Fn() + Send + Sync + 'static,
+    ) -> HookedWeak<T> {
+        this.hooks.retain(|hook| hook.strong_count() > 0);
+        let hook: Arc<Hook> = Arc::new(on_invalidate);
+        this.hooks.push(Arc::downgrade(&hook));
+        HookedWeak {
+            weak: Arc::downgrade(&this.arc),
+            hook,
+        }
+    }
+
+    /// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), and calls the
+    /// callbacks of the handles still alive if the allocation was replaced.
+    ///
+    /// Returns None if the strong count was greater than 1 or a concurrent
+    /// weak upgrade won the race.
+    pub fn get_mut_drop_weak(&mut self) -> Option<&mut T> {
+        match get_mut_drop_weak_access(&mut self.arc) {
+            Access::Unchanged(value) => {
+                // No weak pointers left, so all handles are gone.
+                self.hooks.clear();
+                Some(value)
+            }
+            Access::Replaced(value) => {
+                for hook in self.hooks.drain(..).filter_map(|hook| hook.upgrade()) {
+                    hook();
+                }
+                Some(value)
+            }
+            Access::Shared(_) => None,
+        }
+    }
+}
+
+impl<T> HookedWeak<T> {
+    /// Attempts to upgrade to a strong pointer.
+    pub fn upgrade(&self) -> Option<Arc<T>> {
+        self.weak.upgrade()
+    }
+
+    /// The underlying weak pointer.
+    pub fn as_weak(&self) -> &Weak<T> {
+        &self.weak
+    }
+}
+
+impl<T> From<Arc<T>> for HookedArc<T> {
+    fn from(arc: Arc<T>) -> Self {
+        HookedArc::from_arc(arc)
+    }
+}
+
+impl<T> Clone for HookedWeak<T> {
+    fn clone(&self) -> Self {
+        Self {
+            weak: Weak::clone(&self.weak),
+            hook: Arc::clone(&self.hook),
+        }
+    }
+}
+
+impl<T> Deref for HookedArc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.arc
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for HookedArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> fmt::Debug for HookedWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(HookedWeak)")
+    }
+}
@@ -0,0 +1,107 @@
+//! Hooks reporting the outcomes of the replacement algorithm to whichever
+//! instrumentation features are enabled. Without any, they compile to
+//! nothing.
+
+#![cfg_attr(
+    not(feature = "tracing"),
+    allow(unused_variables, clippy::extra_unused_type_parameters)
+)]
+
+#[cfg(any(feature = "tracing", feature = "log"))]
+use core::any::type_name;
+#[cfg(feature = "call-sites")]
+use core::panic::Location;
+#[cfg(feature = "stats")]
+use core::sync::atomic::Ordering;
+
+#[cfg(feature = "stats")]
+use crate::stats;
+
+/// The pointer was already exclusive.
+#[inline]
+pub(crate) fn fast_path() {
+    #[cfg(feature = "metrics")]
+    metrics::counter!("get_mut_drop_weak.fast_path").increment(1);
+    #[cfg(feature = "stats")]
+    stats::FAST_PATH.fetch_add(1, Ordering::Relaxed);
+}
+
+/// The value was cloned into a new allocation.
+#[inline]
+pub(crate) fn cloned() {
+    #[cfg(feature = "stats")]
+    stats::CLONES.fetch_add(1, Ordering::Relaxed);
+}
+
+/// The value was moved to a new allocation, orphaning `weak` weak pointers.
+#[cfg_attr(feature = "call-sites", track_caller)]
+#[inline]
+pub(crate) fn replaced<T: ?Sized>(weak: usize) {
+    orphaned();
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        value = type_name::<T>(),
+        weak,
+        "replaced the allocation, orphaning weak pointers"
+    );
+    #[cfg(feature = "log")]
+    log::debug!(
+        "replaced the allocation of a {}, orphaning {weak} weak pointers",
+        type_name::<T>()
+    );
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!("get_mut_drop_weak.replacements").increment(1);
+        metrics::histogram!("get_mut_drop_weak.orphaned_weaks").record(weak as f64);
+    }
+    #[cfg(feature = "stats")]
+    stats::REPLACEMENTS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Weak pointers were orphaned, either by [`replaced`] or by a copy. With the
+/// `call-sites` feature, records the caller, which is the public entry point
+/// as long as every function in between is `#[track_caller]` and no closure
+/// sits on the path.
+#[cfg_attr(feature = "call-sites", track_caller)]
+#[inline]
+pub(crate) fn orphaned() {
+    #[cfg(feature = "call-sites")]
+    crate::call_sites::record(Location::caller());
+}
+
+/// A concurrent weak upgrade won the race against a replacement.
+#[inline]
+pub(crate) fn lost_race<T: ?Sized>(strong: usize, weak: usize) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        value = type_name::<T>(),
+        strong,
+        weak,
+        "lost the race against a concurrent weak upgrade"
+    );
+    #[cfg(feature = "log")]
+    log::warn!(
+        "lost the race against a concurrent weak upgrade of a {} (strong = {strong}, weak = {weak})",
+        type_name::<T>()
+    );
+    #[cfg(feature = "metrics")]
+    metrics::counter!("get_mut_drop_weak.lost_races").increment(1);
+    #[cfg(feature = "stats")]
+    stats::FAILURES.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Exclusive access was refused because of other strong pointers.
+#[inline]
+pub(crate) fn strongly_shared<T: ?Sized>(strong: usize, weak: usize) {
+    #[cfg(feature = "tracing")]
+    tracing::trace!(
+        value = type_name::<T>(),
+        strong,
+        weak,
+        "value is strongly shared"
+    );
+    #[cfg(feature = "metrics")]
+    metrics::counter!("get_mut_drop_weak.strongly_shared").increment(1);
+    #[cfg(feature = "stats")]
+    stats::FAILURES.fetch_add(1, Ordering::Relaxed);
+}
@@ -0,0 +1,126 @@
+use alloc::{
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{fmt, hash::BuildHasher};
+use std::{collections::HashMap, hash::RandomState};
+
+use crate::get_mut_drop_weak_unsized;
+
+/// A string interner that doesn't keep its strings alive.
+///
+/// [`Interner::intern`] hands out `Arc<str>`s, and the table only holds weak
+/// pointers to them, so a string is freed once its last `Arc` is dropped.
+/// Its entry then lingers, dead, until its bucket is next looked up or
+/// [`Interner::purge`] runs.
+///
+/// An interned string can be edited in place with [`Interner::get_mut`]
+/// once its holder owns the only `Arc`. This orphans the table's weak
+/// pointer with [`get_mut_drop_weak_unsized`], so the edited string is no
+/// longer interned, and lookups can't return a string whose content changed
+/// under them.
+///
+/// Requires the `std` feature.
+pub struct Interner<S = RandomState> {
+    buckets: HashMap<u64, Vec<Weak<str>>>,
+    hasher: S,
+}
+
+impl Interner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<S: BuildHasher> Interner<S> {
+    /// Creates an empty interner hashing strings with `hasher`.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            buckets: HashMap::new(),
+            hasher,
+        }
+    }
+
+    /// Returns the interned copy of `s`, interning it first if needed.
+    pub fn intern(&mut self, s: &str) -> Arc<str> {
+        let bucket = self.buckets.entry(self.hasher.hash_one(s)).or_default();
+        let mut found = None;
+        bucket.retain(|weak| match weak.upgrade() {
+            Some(arc) => {
+                if found.is_none() && *arc == *s {
+                    found = Some(arc);
+                }
+                true
+            }
+            None => false,
+        });
+        found.unwrap_or_else(|| {
+            let arc = Arc::<str>::from(s);
+            bucket.push(Arc::downgrade(&arc));
+            arc
+        })
+    }
+
+    /// Returns the interned copy of `s`, if there is one.
+    pub fn get(&self, s: &str) -> Option<Arc<str>> {
+        self.buckets
+            .get(&self.hasher.hash_one(s))?
+            .iter()
+            .filter_map(Weak::upgrade)
+            .find(|arc| **arc == *s)
+    }
+
+    /// Exclusive access to `arc` for editing, retiring it from the table.
+    ///
+    /// Returns None if `arc` is strongly shared.
+    pub fn get_mut<'a>(&mut self, arc: &'a mut Arc<str>) -> Option<&'a mut str> {
+        let hash = self.hasher.hash_one(&**arc);
+        let value = get_mut_drop_weak_unsized(arc).ok()?;
+        // The entry for `arc`, if any, was just orphaned.
+        if let Some(bucket) = self.buckets.get_mut(&hash) {
+            bucket.retain(|weak| weak.strong_count() > 0);
+            if bucket.is_empty() {
+                self.buckets.remove(&hash);
+            }
+        }
+        Some(value)
+    }
+
+    /// Removes the entries of strings that have been freed or retired, and
+    /// returns how many there were.
+    pub fn purge(&mut self) -> usize {
+        let mut purged = 0;
+        self.buckets.retain(|_, bucket| {
+            let len = bucket.len();
+            bucket.retain(|weak| weak.strong_count() > 0);
+            purged += len - bucket.len();
+            !bucket.is_empty()
+        });
+        purged
+    }
+
+    /// The number of entries, including dead ones not yet purged.
+    pub fn len(&self) -> usize {
+        self.buckets.values().map(Vec::len).sum()
+    }
+
+    /// Whether there are no entries, dead or alive.
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> fmt::Debug for Interner<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interner")
+            .field("buckets", &self.buckets.len())
+            .finish()
+    }
+}
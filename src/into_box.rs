@@ -0,0 +1,40 @@
+use alloc::boxed::Box;
+
+use crate::{SharedPointer, instrument};
+
+/// Converts a shared pointer into a `Box` if it is the only strong pointer,
+/// regardless of weak pointers, which are orphaned.
+///
+/// The box is allocated before the value is moved out, so an allocation
+/// failure leaves the pointer intact.
+///
+/// Returns Err(ptr) if the strong count was greater than 1 or a concurrent
+/// weak upgrade won the race.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn into_box_drop_weak<T, P: SharedPointer<T>>(mut ptr: P) -> Result<Box<T>, P> {
+    let unique = P::is_unique(&mut ptr);
+    if !unique {
+        let strong = P::strong_count(&ptr);
+        if strong > 1 {
+            instrument::strongly_shared::<T>(strong, P::weak_count(&ptr));
+            return Err(ptr);
+        }
+    }
+    let orphaned = P::weak_count(&ptr);
+    let mut boxed = Box::new_uninit();
+    let value = match P::try_unwrap(ptr) {
+        Ok(value) => value,
+        Err(ptr) => {
+            instrument::lost_race::<T>(P::strong_count(&ptr), P::weak_count(&ptr));
+            return Err(ptr);
+        }
+    };
+    boxed.write(value);
+    if unique {
+        instrument::fast_path();
+    } else {
+        instrument::replaced::<T>(orphaned);
+    }
+    // SAFETY: Just initialized. (`Box::write` needs Rust 1.87.)
+    Ok(unsafe { boxed.assume_init() })
+}
@@ -0,0 +1,25 @@
+use alloc::vec::Vec;
+
+use crate::SharedPointer;
+
+/// Moves the values out of every pointer in `ptrs` that is the only strong
+/// pointer to its value, orphaning any weak pointers, and returns them along
+/// with the pointers that are still shared, each in their original order.
+///
+/// A pointer whose weak pointer is upgraded concurrently ends up with the
+/// shared ones.
+pub fn into_inner_all_drop_weak<T, P: SharedPointer<T>>(ptrs: Vec<P>) -> (Vec<T>, Vec<P>) {
+    let mut values = Vec::with_capacity(ptrs.len());
+    let mut shared = Vec::new();
+    for ptr in ptrs {
+        if P::strong_count(&ptr) > 1 {
+            shared.push(ptr);
+            continue;
+        }
+        match P::try_unwrap(ptr) {
+            Ok(value) => values.push(value),
+            Err(ptr) => shared.push(ptr),
+        }
+    }
+    (values, shared)
+}
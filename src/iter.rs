@@ -0,0 +1,85 @@
+use core::{fmt, iter::FusedIterator, marker::PhantomData};
+
+use crate::{Access, SharedPointer, get_mut_drop_weak_access};
+
+/// Adds [`drop_weak_mut`](Self::drop_weak_mut) to iterators over mutable
+/// references to pointers, such as `slice::IterMut<'_, Arc<T>>`.
+///
+/// ```
+/// use std::sync::Arc;
+/// use get_mut_drop_weak::DropWeakMutExt;
+///
+/// let mut arcs = vec![Arc::new(1), Arc::new(2)];
+/// let _other = Arc::clone(&arcs[1]);
+///
+/// arcs.iter_mut()
+///     .drop_weak_mut()
+///     .filter_map(|access| access.into_result().ok())
+///     .for_each(|value| *value += 10);
+///
+/// assert_eq!(*arcs[0], 11);
+/// assert_eq!(*arcs[1], 2);
+/// ```
+pub trait DropWeakMutExt<'a, T: 'a, P: SharedPointer<T> + 'a>:
+    Iterator<Item = &'a mut P> + Sized
+{
+    /// Calls [`get_mut_drop_weak_access`] on each pointer as it is reached,
+    /// yielding the [`Access`] outcome.
+    fn drop_weak_mut(self) -> DropWeakMut<Self, T> {
+        DropWeakMut {
+            iter: self,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: 'a, P: SharedPointer<T> + 'a, I: Iterator<Item = &'a mut P>> DropWeakMutExt<'a, T, P>
+    for I
+{
+}
+
+/// The iterator returned by [`DropWeakMutExt::drop_weak_mut`].
+pub struct DropWeakMut<I, T> {
+    iter: I,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: 'a, P: SharedPointer<T> + 'a, I: Iterator<Item = &'a mut P>> Iterator
+    for DropWeakMut<I, T>
+{
+    type Item = Access<'a, T, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(get_mut_drop_weak_access)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T: 'a, P: SharedPointer<T> + 'a, I: DoubleEndedIterator<Item = &'a mut P>>
+    DoubleEndedIterator for DropWeakMut<I, T>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(get_mut_drop_weak_access)
+    }
+}
+
+impl<'a, T: 'a, P: SharedPointer<T> + 'a, I: ExactSizeIterator<Item = &'a mut P>> ExactSizeIterator
+    for DropWeakMut<I, T>
+{
+}
+
+impl<'a, T: 'a, P: SharedPointer<T> + 'a, I: FusedIterator<Item = &'a mut P>> FusedIterator
+    for DropWeakMut<I, T>
+{
+}
+
+impl<I: fmt::Debug, T> fmt::Debug for DropWeakMut<I, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DropWeakMut")
+            .field("iter", &self.iter)
+            .finish()
+    }
+}
@@ -1,11 +1,272 @@
-use std::{mem::MaybeUninit, ptr, sync::Arc};
+//! Safely get a mutable reference to the contents of an [`Arc`](alloc::sync::Arc)
+//! (or [`Rc`](alloc::rc::Rc)) that is only weakly shared, by dissociating any
+//! outstanding weak references.
+//!
+//! The crate is `#![no_std]` and only requires `alloc`. The `std` feature is
+//! enabled by default and only pulls in `std` for APIs that need it.
+//!
+//! The `nightly-allocator` feature requires a nightly compiler and adds
+//! support for `Arc<T, A>` and `Rc<T, A>` with custom allocators. On stable,
+//! the `allocator-api2` feature provides an entry point for `Arc` forks built
+//! on the `allocator-api2` crate.
+//!
+//! The `arc-swap` feature adds [`rcu_mut_drop_weak`], a read-copy-update loop
+//! for values stored in an `ArcSwap`.
+//!
+//! The `call-sites` feature records the source location of the most recent
+//! calls that orphaned weak pointers, read with [`orphaning_call_sites`], to
+//! find the call site that keeps invalidating weak caches. The main entry
+//! points are `#[track_caller]` in that mode.
+//!
+//! The `derive` feature adds `#[derive(GetMutAll)]` for structs of `Arc`
+//! fields, which generates a `get_mut_all` method making every field
+//! exclusive at once, like [`get_mut_all!`].
+//!
+//! The `event-listener` feature adds [`ReleaseEvent`], which lets readers wake
+//! a blocked thread or task when they drop their pointers, with any async
+//! runtime.
+//!
+//! The `log` feature logs a debug record when a replacement orphans weak
+//! pointers, and a warning when a concurrent weak upgrade wins the race, for
+//! projects that use `log` rather than `tracing`.
+//!
+//! The `lru` feature adds the [`lru`](crate::lru) module, a cache that
+//! orphans the weak observers of an entry when it is mutated or recycled.
+//!
+//! The `metrics` feature records counters through the `metrics` facade:
+//! `get_mut_drop_weak.fast_path` for pointers that were already exclusive,
+//! `get_mut_drop_weak.replacements`, `get_mut_drop_weak.lost_races` and
+//! `get_mut_drop_weak.strongly_shared`, along with a
+//! `get_mut_drop_weak.orphaned_weaks` histogram of the weak pointers orphaned
+//! by each replacement.
+//!
+//! The `parking_lot` feature adds the [`parking_lot`](crate::parking_lot)
+//! module, with versions of [`lock_or_own`](crate::parking_lot::lock_or_own)
+//! and [`write_or_own`](crate::parking_lot::write_or_own) for `parking_lot`
+//! locks.
+//!
+//! The `rayon` feature adds [`par_get_mut_drop_weak_batch`] and
+//! [`par_make_unique_all_drop_weak`], which spread the batch functions across
+//! the rayon thread pool.
+//!
+//! The `stats` feature keeps process-wide counts of fast-path hits,
+//! replacements, clones and failures, read with [`stats`] and cleared with
+//! [`reset_stats`], for projects without a metrics stack.
+//!
+//! The `tokio` feature adds the [`tokio`](crate::tokio) module, with an
+//! async version of [`lock_or_own`](crate::tokio::lock_or_own) for `tokio`
+//! mutexes.
+//!
+//! The `tracing` feature emits `tracing` events when a replacement orphans
+//! weak pointers (at debug level, with the number orphaned), when a
+//! concurrent weak upgrade wins the race (debug), and when a pointer is
+//! strongly shared (trace), from [`get_mut_drop_weak`] and the functions built
+//! on it.
+//!
+//! The `weak-audit` feature adds [`AuditedArc`], which captures a backtrace
+//! for every weak pointer created from it, to track down unexpected weak
+//! pointers during debugging.
+//!
+//! The `nightly` feature implies `nightly-allocator` and additionally uses
+//! unstable `Arc` and `Rc` APIs, such as `get_mut_unchecked`, in place of the
+//! crate's stable equivalents.
+//!
+//! The minimum supported Rust version is 1.85, the first release with edition
+//! 2024. Every stable API the crate relies on, such as `Arc::new_uninit`
+//! (1.82), is older than that.
+//!
+//! The `safe` feature adds the [`safe`](crate::safe) module, with versions of
+//! the main functions written without unsafe code, which need `T: Default`
+//! or `T: Clone` to orphan weak pointers. It changes nothing else, so
+//! libraries can enable it too.
+#![no_std]
+#![cfg_attr(feature = "nightly-allocator", feature(allocator_api))]
+#![cfg_attr(feature = "nightly", feature(get_mut_unchecked))]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod access;
+mod all;
+#[cfg(feature = "allocator-api2")]
+pub mod allocator_api2;
+mod any;
+#[cfg(feature = "arc-swap")]
+mod arc_swap;
+#[cfg(feature = "weak-audit")]
+mod audit;
+mod batch;
+#[cfg(feature = "std")]
+mod blocking;
+mod boxed;
+mod cache;
+#[cfg(feature = "call-sites")]
+mod call_sites;
+mod cell;
+mod clone_out;
+mod clone_replace;
+mod copy;
+mod diagnosis;
+mod dst;
+mod epoch;
+mod error;
+mod exclusivity;
+mod ext;
+mod fallback;
+mod future;
+mod guard;
+mod guarded;
+mod hooked;
+mod instrument;
+#[cfg(feature = "std")]
+mod interner;
+mod into_box;
+mod into_inner;
+mod iter;
+#[cfg(feature = "std")]
+mod lock;
+#[cfg(feature = "lru")]
+pub mod lru;
+mod make_mut;
+mod map;
+mod mut_guard;
+mod no_weak;
+mod observable;
+mod options;
+mod own_or;
+#[cfg(feature = "parking_lot")]
+pub mod parking_lot;
+mod pin;
+mod pointer;
+mod policy;
+mod pool;
+#[cfg(feature = "std")]
+mod proxy;
+mod purge;
+#[cfg(feature = "rayon")]
+mod rayon;
+pub mod rc;
+mod registry;
+#[cfg(feature = "event-listener")]
+mod release;
+mod relocate;
+mod repair;
+mod retry;
+#[cfg(feature = "safe")]
+pub mod safe;
+mod slot;
+mod spin;
+mod stable;
+#[cfg(feature = "stats")]
+mod stats;
+mod strict;
+mod take;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+pub mod tree;
+mod uninit;
+mod unique;
+mod update;
+#[cfg(feature = "std")]
+mod weak_cache;
+
+pub use access::{
+    Access, get_mut_drop_weak_access, get_mut_drop_weak_counted, get_mut_drop_weak_with_old,
+};
+pub use all::{PointerTuple, get_many_mut, get_mut_drop_weak2};
+pub use any::get_mut_drop_weak_any;
+#[cfg(feature = "arc-swap")]
+pub use arc_swap::rcu_mut_drop_weak;
+#[cfg(feature = "weak-audit")]
+pub use audit::{AuditedArc, AuditedWeak};
+#[cfg(feature = "std")]
+pub use batch::make_unique_all_drop_weak;
+pub use batch::{BatchOutcome, first_exclusive, get_mut_drop_weak_batch};
+#[cfg(feature = "std")]
+pub use blocking::{MAX_POLL_INTERVAL, get_mut_drop_weak_blocking, with_exclusive};
+pub use boxed::get_mut_drop_weak_boxed;
+pub use cache::{ReplacementCache, get_mut_drop_weak_cached};
+#[cfg(feature = "call-sites")]
+pub use call_sites::{CALL_SITE_CAPACITY, clear_orphaning_call_sites, orphaning_call_sites};
+pub use cell::ArcCell;
+pub use clone_out::{CloneOut, MutOrClone, get_mut_or_clone_out};
+pub use clone_replace::clone_replace_drop_weak;
+pub use copy::get_mut_drop_weak_copy;
+pub use diagnosis::{Diagnosis, explain};
+pub use dst::{Rebuild, get_mut_drop_weak_unsized};
+pub use epoch::{EpochArc, EpochWeak};
+pub use error::{AllocFailed, FailureReason, FieldBlocked, GetMutError, Timeout, TryGetMutError};
+pub use exclusivity::{Exclusivity, exclusivity};
+pub use ext::ArcGetMutExt;
+pub use fallback::DropWeakResultExt;
+pub use future::{GetMutDropWeak, get_mut_drop_weak_async, get_mut_drop_weak_async_with};
+#[cfg(feature = "derive")]
+pub use get_mut_drop_weak_derive::GetMutAll;
+pub use guarded::{GuardedArc, GuardedWeak};
+pub use hooked::{HookedArc, HookedWeak};
+#[cfg(feature = "std")]
+pub use interner::Interner;
+pub use into_box::into_box_drop_weak;
+pub use into_inner::into_inner_all_drop_weak;
+pub use iter::{DropWeakMut, DropWeakMutExt};
+#[cfg(feature = "std")]
+pub use lock::{OwnOrLock, OwnOrWrite, lock_or_own, write_or_own};
+pub use make_mut::make_mut_drop_weak;
+pub use map::get_mut_drop_weak_map;
+pub use mut_guard::{ArcMutGuard, get_mut_drop_weak_guard};
+pub use no_weak::NoWeakArc;
+pub use observable::ObservableArc;
+pub use options::{GetMutOptions, get_mut_with};
+pub use own_or::OwnOr;
+pub use pin::{get_mut_drop_weak_pinned, get_mut_drop_weak_pinned_unchecked};
+pub use pointer::SharedPointer;
+use pointer::sealed::Sealed;
+#[cfg(feature = "std")]
+pub use policy::ReplaceOrWait;
+pub use policy::{
+    CloneBelow, Reclaim, ReclaimPolicy, ReplaceOrClone, ReplaceWeak, get_mut_with_policy,
+};
+pub use pool::{ArcPool, get_mut_drop_weak_pooled};
+#[cfg(feature = "std")]
+pub use proxy::{ProxyArc, ProxyMut, ProxyWeak};
+pub use purge::PurgeDeadWeaks;
+#[cfg(feature = "rayon")]
+pub use rayon::{par_get_mut_drop_weak_batch, par_make_unique_all_drop_weak};
+pub use registry::{RegisteredWeak, WeakRegistry};
+#[cfg(feature = "event-listener")]
+pub use release::ReleaseEvent;
+pub use relocate::{Relocate, get_mut_drop_weak_dyn, relocate};
+pub use repair::{RepairSelfWeak, update_cyclic, with_exclusive_fixup, with_exclusive_repair};
+pub use retry::get_mut_drop_weak_retry;
+pub use slot::get_mut_or_insert_with;
+pub use stable::get_mut_stable;
+#[cfg(feature = "stats")]
+pub use stats::{Stats, reset_stats, stats};
+pub use strict::{NoSelfWeak, OrphanWeaks, get_mut_drop_weak_authorized, get_mut_drop_weak_strict};
+pub use take::{replace_drop_weak, take_drop_weak};
+pub use uninit::{get_mut_drop_weak_uninit, get_mut_drop_weak_uninit_slice};
+pub use unique::{UniqueArc, into_unique};
+pub use update::update_with;
+pub use update::{UpdateError, try_update};
+#[cfg(feature = "std")]
+pub use weak_cache::refresh_cache_entry;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::all::{check_strong, exclusive, make_exclusive};
+}
 
 /// Attempts to get a mutable reference to the inner data of an Arc.
 ///
+/// This works for any [`SharedPointer`], i.e. both [`Arc`](alloc::sync::Arc)
+/// and [`Rc`](alloc::rc::Rc). The description below is in terms of `Arc`; for
+/// `Rc` the concurrent-upgrade failure mode cannot occur.
+///
 /// If the Arc has a strong count of 1 and a weak count of 0, it returns
 /// the mutable reference directly.
 ///
-/// If the Arc has a strong count greater than 1, it returns None.
+/// If the Arc has a strong count greater than 1, it returns Err.
 ///
 /// If the Arc has a strong count of 1 and a weak count greater than 0,
 /// it attempts to replace the Arc instance with a new one containing the
@@ -13,8 +274,14 @@ use std::{mem::MaybeUninit, ptr, sync::Arc};
 /// involves an internal allocation for the new Arc instance. If this
 /// allocation fails, the function will panic (before modifying the input Arc).
 ///
+/// For zero-sized types, such as marker types used as tokens, moving the value
+/// is free, so the replacement only costs an allocation for the reference
+/// counts. The race against concurrent upgrades still has to be decided,
+/// because duplicating a zero-sized value is only sound if it is `Copy` (see
+/// [`get_mut_drop_weak_copy`]).
+///
 /// Returns Ok(&mut T) on success, or Err(&mut Arc<T>) if the strong count was
-/// greater than 1.
+/// greater than 1 or a concurrent weak upgrade won the race.
 ///
 /// The Err variant is useful for the caller to avoid borrow-checker issues
 /// due to rust's lack of non-lexical lifetimes. That is, if the caller
@@ -25,76 +292,233 @@ use std::{mem::MaybeUninit, ptr, sync::Arc};
 /// something else.
 ///
 /// (See https://rust-lang.github.io/rfcs/2094-nll.html#problem-case-2-conditional-control-flow)
+#[cfg_attr(feature = "call-sites", track_caller)]
+#[inline]
+pub fn get_mut_drop_weak<T, P: SharedPointer<T>>(ptr: &mut P) -> Result<&mut T, &mut P> {
+    get_mut_drop_weak_detailed(ptr).map_err(GetMutError::into_inner)
+}
+
+/// Like [`get_mut_drop_weak`], but on failure reports the observed counts and
+/// whether the pointer was strongly shared or a concurrent weak upgrade won
+/// the race.
+#[cfg_attr(feature = "call-sites", track_caller)]
+#[inline]
+pub fn get_mut_drop_weak_detailed<T, P: SharedPointer<T>>(
+    ptr: &mut P,
+) -> Result<&mut T, GetMutError<'_, P>> {
+    if is_exclusive(ptr) {
+        // SAFETY: `is_exclusive` just confirmed there are no other pointers,
+        // and none can be created while `ptr` is mutably borrowed.
+        return Ok(unsafe { P::get_mut_unchecked(ptr) });
+    }
+    detailed_slow(ptr, &mut None)
+}
+
+/// The slow path of [`get_mut_drop_weak_detailed`], kept out of line so that
+/// callers that almost always hit the exclusive case stay small.
+///
+/// If `spare` holds a replacement allocation that can be reused for `ptr`, it
+/// is used instead of allocating a new one. If a concurrent weak upgrade wins
+/// the race, the unused allocation is left in `spare`.
 //
 // # Safety Notes
 // This function uses unsafe code internally to handle the Arc replacement
 // while aiming to be panic-safe *after* the initial allocation check.
-// It relies on ptr::read/write and careful state management.
-pub fn get_mut_drop_weak<T>(arc: &mut Arc<T>) -> Result<&mut T, &mut Arc<T>> {
-    // Handle easy cases first without allocation
-    if Arc::get_mut(arc).is_some() {
-        // Strong=1, Weak=0. Already exclusive.
-        // Need to call it again to get the reference with the right lifetime.
-        return Ok(unsafe { get_mut_unchecked(arc) });
-    }
-    if Arc::strong_count(arc) > 1 {
+// It relies on ptr::read/write (see `guard::moved_out`) and careful state
+// management.
+#[cfg_attr(feature = "call-sites", track_caller)]
+#[cold]
+#[inline(never)]
+pub(crate) fn detailed_slow<'a, T, P: SharedPointer<T>>(
+    ptr: &'a mut P,
+    spare: &mut Option<P::Uninit>,
+) -> Result<&'a mut T, GetMutError<'a, P>> {
+    let strong = P::strong_count(ptr);
+    if strong > 1 {
         // Strong > 1. Cannot get exclusive access.
-        return Err(arc);
+        let weak = P::weak_count(ptr);
+        instrument::strongly_shared::<T>(strong, weak);
+        return Err(GetMutError::new(
+            ptr,
+            strong,
+            weak,
+            FailureReason::StronglyShared,
+        ));
     }
 
-    // State: Strong = 1, Weak > 0. Need to replace the Arc instance.
+    // State: Strong = 1, Weak > 0. Need to replace the pointer instance.
 
     // --- Potentially panicking allocation happens here ---
-    // Pre-allocate storage for the new Arc. If this fails, we panic *before*
-    // entering the unsafe block or modifying `arc`, which is safe for the caller.
-    let mut preallocated_arc: Arc<MaybeUninit<T>> = Arc::new_uninit();
+    // Pre-allocate storage for the new instance. If this fails, we panic *before*
+    // entering the unsafe block or modifying `ptr`, which is safe for the caller.
+    let preallocated = match spare.take() {
+        Some(uninit) if P::can_reuse(ptr, &uninit) => uninit,
+        _ => P::new_uninit(ptr),
+    };
     // --- Allocation succeeded ---
+    let orphaned = P::weak_count(ptr);
+
+    let unused = guard::moved_out(ptr, |original| match P::try_unwrap(original) {
+        // Got the value, old weak pointers are now orphaned. Move the value
+        // into the pre-allocated memory.
+        // SAFETY: `preallocated` came from `new_uninit`, possibly for another
+        // pointer in the same allocator, and was never shared.
+        Ok(value) => (unsafe { P::init(preallocated, value) }, None),
+        // Another thread upgraded a weak reference first.
+        Err(restored) => (restored, Some(preallocated)),
+    });
+    if unused.is_some() {
+        *spare = unused;
+        let (strong, weak) = (P::strong_count(ptr), P::weak_count(ptr));
+        instrument::lost_race::<T>(strong, weak);
+        return Err(GetMutError::new(ptr, strong, weak, FailureReason::LostRace));
+    }
+    instrument::replaced::<T>(orphaned);
+    // Return mutable reference from the new instance. Guaranteed safe.
+    // SAFETY: `ptr` was just replaced by a fresh, unshared pointer.
+    Ok(unsafe { P::get_mut_unchecked(ptr) })
+}
+
+/// Like [`get_mut_drop_weak`], but returns
+/// [`TryGetMutError::AllocFailed`] instead of panicking if the replacement
+/// can't be allocated.
+///
+/// With the `nightly-allocator` feature, the replacement is allocated with
+/// `try_new_uninit_in`. Otherwise, stable pointer types have no fallible
+/// constructor, so the global allocator is first probed for a block of the
+/// needed size. That catches sizes it can't satisfy, but if another thread
+/// exhausts memory between the probe and the real allocation, the usual
+/// allocation error handler still runs.
+#[cfg_attr(feature = "call-sites", track_caller)]
+#[inline]
+pub fn try_get_mut_drop_weak<T, P: SharedPointer<T>>(
+    ptr: &mut P,
+) -> Result<&mut T, TryGetMutError<'_, P>> {
+    if is_exclusive(ptr) {
+        // SAFETY: `is_exclusive` just confirmed there are no other pointers,
+        // and none can be created while `ptr` is mutably borrowed.
+        return Ok(unsafe { P::get_mut_unchecked(ptr) });
+    }
+    try_slow(ptr)
+}
+
+/// The slow path of [`try_get_mut_drop_weak`].
+#[cfg_attr(feature = "call-sites", track_caller)]
+#[cold]
+#[inline(never)]
+fn try_slow<T, P: SharedPointer<T>>(ptr: &mut P) -> Result<&mut T, TryGetMutError<'_, P>> {
+    let strong = P::strong_count(ptr);
+    if strong > 1 {
+        let weak = P::weak_count(ptr);
+        instrument::strongly_shared::<T>(strong, weak);
+        return Err(TryGetMutError::Shared(GetMutError::new(
+            ptr,
+            strong,
+            weak,
+            FailureReason::StronglyShared,
+        )));
+    }
 
-    // Unsafe block to perform the swap without panicking mid-state-change.
-    unsafe {
-        // Read the original Arc out, leaving `arc` pointing to invalid memory temporarily.
-        let original_arc = ptr::read(ptr::from_mut(arc));
-
-        // Consume the original Arc to get the value. Should succeed unless another thread
-        // upgraded a weak reference to a strong one in parallel.
-        match Arc::try_unwrap(original_arc) {
-            Ok(value) => {
-                // Got the value, old weak pointers are now orphaned.
-
-                // Initialize the pre-allocated memory.
-                // get_mut is guaranteed safe because preallocated_arc count is 1.
-                let slot = get_mut_unchecked(&mut preallocated_arc);
-                slot.write(value); // Moves value, initializes memory.
-
-                // Convert Arc<MaybeUninit<T>> -> Arc<T>
-                let final_arc = preallocated_arc.assume_init();
-                // `preallocated_arc` is now consumed.
-
-                // Write the new Arc<T> back into the user's reference location.
-                ptr::write(arc, final_arc); // Consumes final_arc.
-
-                // Return mutable reference from the new Arc. Guaranteed safe.
-                // SAFETY: We just wrote a valid Arc<T> to `arc`.
-                Ok(get_mut_unchecked(arc))
-            }
-            Err(restored_arc) => {
-                // Failed to unwrap, meaning another thread upgraded a weak reference.
-                ptr::write(arc, restored_arc); // Consumes restored_arc.
-                Err(arc) // Indicate failure.
-            }
-        }
+    let Ok(preallocated) = P::try_new_uninit(ptr) else {
+        return Err(TryGetMutError::AllocFailed(ptr));
+    };
+    let orphaned = P::weak_count(ptr);
+
+    // SAFETY: `preallocated` came straight from `try_new_uninit` and was never shared.
+    if unsafe {
+        replace(ptr, preallocated, P::try_unwrap, |u, value| {
+            P::init(u, value)
+        })
+    } {
+        instrument::replaced::<T>(orphaned);
+        // SAFETY: `replace` just wrote a fresh, unshared pointer to `ptr`.
+        Ok(unsafe { P::get_mut_unchecked(ptr) })
+    } else {
+        let (strong, weak) = (P::strong_count(ptr), P::weak_count(ptr));
+        instrument::lost_race::<T>(strong, weak);
+        Err(TryGetMutError::Shared(GetMutError::new(
+            ptr,
+            strong,
+            weak,
+            FailureReason::LostRace,
+        )))
     }
 }
 
-/// Use [`Arc::get_mut_unchecked`] when stable.
+/// Whether `ptr` is the only strong pointer and there are no weak ones, in
+/// which case the caller may take the value with `P::get_mut_unchecked`.
 ///
-/// ```compile_fail
-/// use std::sync::Arc;
-/// let mut a = Arc::new(0usize);
-/// let b = unsafe { Arc::get_mut_unchecked(&mut a) };
-/// *b += 1;
-/// ```
-unsafe fn get_mut_unchecked<T>(this: &mut Arc<T>) -> &mut T {
-    let ptr = Arc::as_ptr(this);
-    unsafe { &mut *ptr.cast_mut() }
+/// This is the fast path shared by the entry points. Uniqueness is decided by
+/// a single `get_mut`-style check (for `Arc`, one compare-exchange and one
+/// load), and the reference is then produced without touching the counts
+/// again. Matching on `get_mut` directly would run into the borrow-checker
+/// limitation described on [`get_mut_drop_weak`].
+#[inline]
+pub(crate) fn is_exclusive<T, P: SharedPointer<T>>(ptr: &mut P) -> bool {
+    let unique = P::is_unique(ptr);
+    if unique {
+        // Strong=1, Weak=0. Already exclusive.
+        instrument::fast_path();
+    }
+    unique
+}
+
+/// Returns the value of `ptr` if [`is_exclusive`], and otherwise hands `ptr`
+/// to `slow`.
+///
+/// Paths that can orphan weak pointers check [`is_exclusive`] themselves
+/// instead, as `#[track_caller]` doesn't reach through `slow` to the
+/// call-site hook.
+#[inline]
+pub(crate) fn unique_or_else<'a, T, P: SharedPointer<T>, E>(
+    ptr: &'a mut P,
+    slow: impl FnOnce(&'a mut P) -> Result<&'a mut T, E>,
+) -> Result<&'a mut T, E> {
+    if is_exclusive(ptr) {
+        // SAFETY: `is_exclusive` just confirmed there are no other pointers,
+        // and none can be created while `ptr` is mutably borrowed.
+        return Ok(unsafe { P::get_mut_unchecked(ptr) });
+    }
+    slow(ptr)
+}
+
+/// Moves the value out of `ptr`, whose strong count must be 1, and into a new
+/// pointer built by `init` from `preallocated`, orphaning all weak pointers.
+///
+/// Returns false, leaving `ptr` untouched, if `try_unwrap` fails because
+/// another thread upgraded a weak reference in the meantime.
+///
+/// If `try_unwrap` or `init` panics, the process is aborted.
+///
+/// # Safety
+/// `init` must be safe to call with `preallocated`.
+//
+// # Interleavings
+// While we hold the only strong pointer, the only other operation that can
+// touch the strong count is `Weak::upgrade`, which increments it unless it is
+// 0. `try_unwrap` decides the race with a single compare-exchange from 1 to 0:
+// - An upgrade that lands before the compare-exchange makes it fail. The
+//   original pointer is written back unchanged and the upgrader shares the
+//   original value.
+// - An upgrade that lands after it sees a strong count of 0 and returns
+//   `None`, so it never observes the moved-out value.
+// Cloning or dropping weak pointers only touches the weak count and can't
+// interfere either way.
+//
+// `Arc::into_inner` can't replace `try_unwrap` here: when it loses the race it
+// still consumes the pointer (the value then lives on with the upgrader), so
+// there would be nothing left to write back and hand to the caller as `Err`.
+unsafe fn replace<T, P, U>(
+    ptr: &mut P,
+    preallocated: U,
+    try_unwrap: impl FnOnce(P) -> Result<T, P>,
+    init: impl FnOnce(U, T) -> P,
+) -> bool {
+    guard::moved_out(ptr, |original| match try_unwrap(original) {
+        // Got the value, old weak pointers are now orphaned. Move the value
+        // into the pre-allocated memory.
+        Ok(value) => (init(preallocated, value), true),
+        // Another thread upgraded a weak reference first.
+        Err(restored) => (restored, false),
+    })
 }
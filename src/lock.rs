@@ -0,0 +1,72 @@
+use alloc::sync::Arc;
+use std::sync::{LockResult, Mutex, MutexGuard, PoisonError, RwLock, RwLockWriteGuard};
+
+use crate::{OwnOr, get_mut_drop_weak};
+
+/// The result of [`lock_or_own`]: exclusive access to the value in a
+/// `Mutex`, with or without the lock.
+pub type OwnOrLock<'a, T> = OwnOr<'a, T, MutexGuard<'a, T>>;
+
+/// The result of [`write_or_own`]: exclusive access to the value in an
+/// `RwLock`, with or without the lock.
+pub type OwnOrWrite<'a, T> = OwnOr<'a, T, RwLockWriteGuard<'a, T>>;
+
+/// Exclusive access to the value in `arc`'s `Mutex`, skipping the lock when
+/// the `Arc` can be made exclusive with
+/// [`get_mut_drop_weak`](crate::get_mut_drop_weak), orphaning weak pointers
+/// as needed, and locking it otherwise.
+///
+/// Returns a poison error, as [`Mutex::lock`] does, if a thread panicked
+/// while holding the lock.
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use get_mut_drop_weak::{OwnOrLock, lock_or_own};
+///
+/// let mut arc = Arc::new(Mutex::new(vec![1]));
+/// lock_or_own(&mut arc).unwrap().push(2);
+///
+/// let other = Arc::clone(&arc);
+/// let mut value = lock_or_own(&mut arc).unwrap();
+/// assert!(matches!(value, OwnOrLock::Locked(_)));
+/// value.push(3);
+/// drop(value);
+/// assert_eq!(*other.lock().unwrap(), [1, 2, 3]);
+/// ```
+///
+/// Requires the `std` feature.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn lock_or_own<T>(arc: &mut Arc<Mutex<T>>) -> LockResult<OwnOrLock<'_, T>> {
+    match get_mut_drop_weak(arc) {
+        Ok(mutex) => match mutex.get_mut() {
+            Ok(value) => Ok(OwnOrLock::Own(value)),
+            Err(err) => Err(PoisonError::new(OwnOrLock::Own(err.into_inner()))),
+        },
+        Err(arc) => match arc.lock() {
+            Ok(guard) => Ok(OwnOrLock::Locked(guard)),
+            Err(err) => Err(PoisonError::new(OwnOrLock::Locked(err.into_inner()))),
+        },
+    }
+}
+
+/// Like [`lock_or_own`] for an `RwLock`: exclusive access to its value,
+/// skipping the lock when the `Arc` can be made exclusive, and taking the
+/// write lock otherwise.
+///
+/// Returns a poison error, as [`RwLock::write`] does, if a thread panicked
+/// while holding the write lock.
+///
+/// Requires the `std` feature.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn write_or_own<T>(arc: &mut Arc<RwLock<T>>) -> LockResult<OwnOrWrite<'_, T>> {
+    match get_mut_drop_weak(arc) {
+        Ok(lock) => match lock.get_mut() {
+            Ok(value) => Ok(OwnOrWrite::Own(value)),
+            Err(err) => Err(PoisonError::new(OwnOrWrite::Own(err.into_inner()))),
+        },
+        Err(arc) => match arc.write() {
+            Ok(guard) => Ok(OwnOrWrite::Locked(guard)),
+            Err(err) => Err(PoisonError::new(OwnOrWrite::Locked(err.into_inner()))),
+        },
+    }
+}
@@ -0,0 +1,162 @@
+//! A least-recently-used cache of [`Arc`]s whose weak observers are orphaned
+//! when an entry is mutated or recycled.
+//!
+//! Requires the `lru` feature.
+
+use alloc::{
+    collections::BTreeMap,
+    sync::{Arc, Weak},
+};
+use core::{fmt, hash::Hash};
+use std::collections::HashMap;
+
+use crate::get_mut_drop_weak;
+
+struct Entry<V> {
+    arc: Arc<V>,
+    tick: u64,
+}
+
+/// A cache holding up to `capacity` values, evicting the least recently used
+/// one when full.
+///
+/// Values are handed out as strong pointers ([`LruCache::get`]) or weak
+/// observers ([`LruCache::observe`]). [`LruCache::get_mut`] and overwriting
+/// an entry with [`LruCache::insert`] mutate the value in place with
+/// [`get_mut_drop_weak`](crate::get_mut_drop_weak), and an insertion into a
+/// full cache reuses the allocation of the evicted entry the same way. Either
+/// way, the observers of the old value stop upgrading at exactly that point,
+/// instead of seeing a new value at an old address. If a value is still
+/// strongly held outside the cache, a new allocation is used instead, and the
+/// holders keep the old value.
+pub struct LruCache<K, V> {
+    map: HashMap<K, Entry<V>>,
+    order: BTreeMap<u64, K>,
+    tick: u64,
+    capacity: usize,
+}
+
+impl<K: Eq + Hash + Clone, V> LruCache<K, V> {
+    /// Creates an empty cache. A `capacity` of 0 is treated as 1.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            map: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// The maximum number of entries.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of entries.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Whether `key` is cached, without marking it as used.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// A strong pointer to the value for `key`, marking it as used.
+    ///
+    /// While it is alive, the value can't be mutated or recycled in place.
+    pub fn get(&mut self, key: &K) -> Option<Arc<V>> {
+        self.touch(key).map(|entry| Arc::clone(&entry.arc))
+    }
+
+    /// A weak pointer to the value for `key`, marking it as used. It stops
+    /// upgrading once the value is mutated, recycled or dropped.
+    pub fn observe(&mut self, key: &K) -> Option<Weak<V>> {
+        self.touch(key).map(|entry| Arc::downgrade(&entry.arc))
+    }
+
+    /// Exclusive access to the value for `key`, marking it as used and
+    /// orphaning its observers.
+    ///
+    /// Returns None if `key` isn't cached, or if the value is strongly held
+    /// outside the cache or a concurrent upgrade won the race.
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        get_mut_drop_weak(&mut self.touch(key)?.arc).ok()
+    }
+
+    /// Caches `value` for `key`, marking it as used, and returns the key of
+    /// the entry evicted to make room, if any.
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    pub fn insert(&mut self, key: K, value: V) -> Option<K> {
+        if let Some(entry) = self.touch(&key) {
+            match get_mut_drop_weak(&mut entry.arc) {
+                Ok(slot) => *slot = value,
+                Err(arc) => *arc = Arc::new(value),
+            }
+            return None;
+        }
+        let (arc, evicted) = if self.map.len() >= self.capacity {
+            let (_, evicted) = self.order.pop_first().expect("cache is full");
+            let mut entry = self.map.remove(&evicted).expect("ordered key is cached");
+            // Recycle the allocation if nothing outside the cache holds it.
+            let unused = match get_mut_drop_weak(&mut entry.arc) {
+                Ok(slot) => {
+                    *slot = value;
+                    None
+                }
+                Err(_) => Some(value),
+            };
+            let arc = match unused {
+                None => entry.arc,
+                Some(value) => Arc::new(value),
+            };
+            (arc, Some(evicted))
+        } else {
+            (Arc::new(value), None)
+        };
+        self.tick += 1;
+        self.order.insert(self.tick, key.clone());
+        self.map.insert(
+            key,
+            Entry {
+                arc,
+                tick: self.tick,
+            },
+        );
+        evicted
+    }
+
+    /// Removes the entry for `key`, returning its value.
+    pub fn remove(&mut self, key: &K) -> Option<Arc<V>> {
+        let entry = self.map.remove(key)?;
+        self.order.remove(&entry.tick);
+        Some(entry.arc)
+    }
+
+    fn touch(&mut self, key: &K) -> Option<&mut Entry<V>> {
+        let entry = self.map.get_mut(key)?;
+        let key = self
+            .order
+            .remove(&entry.tick)
+            .expect("cached key is ordered");
+        self.tick += 1;
+        entry.tick = self.tick;
+        self.order.insert(self.tick, key);
+        Some(entry)
+    }
+}
+
+impl<K, V> fmt::Debug for LruCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LruCache")
+            .field("len", &self.map.len())
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
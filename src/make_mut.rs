@@ -0,0 +1,14 @@
+use crate::{DropWeakResultExt, SharedPointer, get_mut_drop_weak};
+
+/// Like [`Arc::make_mut`](alloc::sync::Arc::make_mut), but never clones just
+/// because of weak pointers.
+///
+/// If the Arc has a strong count of 1, this behaves like
+/// [`get_mut_drop_weak`], moving the value into a new allocation if there are
+/// weak pointers. Otherwise (including when a concurrent weak upgrade wins the
+/// race), the value is cloned into a new allocation, as with `make_mut`.
+/// Either way, the returned reference is exclusive.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn make_mut_drop_weak<T: Clone, P: SharedPointer<T>>(ptr: &mut P) -> &mut T {
+    get_mut_drop_weak(ptr).or_clone()
+}
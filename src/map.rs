@@ -0,0 +1,14 @@
+use crate::{SharedPointer, get_mut_drop_weak};
+
+/// Like [`get_mut_drop_weak`], but narrows the result to part of the value
+/// with `proj`.
+///
+/// `proj` is only called on success, so the Err variant still hands back the
+/// pointer itself.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_map<'a, T: 'a, U: ?Sized, P: SharedPointer<T>>(
+    ptr: &'a mut P,
+    proj: impl FnOnce(&'a mut T) -> &'a mut U,
+) -> Result<&'a mut U, &'a mut P> {
+    get_mut_drop_weak(ptr).map(proj)
+}
@@ -0,0 +1,74 @@
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{SharedPointer, get_mut_drop_weak};
+
+/// Exclusive access to the value behind a pointer, as obtained from
+/// [`get_mut_drop_weak_guard`].
+///
+/// Unlike a plain `&mut T`, the guard can be narrowed to part of the value
+/// with [`ArcMutGuard::map`], in the style of `MappedMutexGuard`, and passed
+/// around or stored in a struct field as a single named type.
+pub struct ArcMutGuard<'a, T: ?Sized> {
+    value: &'a mut T,
+}
+
+impl<'a, T: ?Sized> ArcMutGuard<'a, T> {
+    /// Narrows the guard to part of the value.
+    pub fn map<U: ?Sized>(this: Self, f: impl FnOnce(&mut T) -> &mut U) -> ArcMutGuard<'a, U> {
+        ArcMutGuard {
+            value: f(this.value),
+        }
+    }
+
+    /// Narrows the guard to part of the value if `f` returns `Some`, or hands
+    /// the guard back otherwise.
+    pub fn try_map<U: ?Sized>(
+        this: Self,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Result<ArcMutGuard<'a, U>, Self> {
+        let value: *mut T = this.value;
+        // SAFETY: `this.value` isn't used again while the reference passed
+        // to `f` is alive. This works around the lack of conditional borrow
+        // returns (NLL problem case 3).
+        match f(unsafe { &mut *value }) {
+            Some(value) => Ok(ArcMutGuard { value }),
+            None => Err(this),
+        }
+    }
+
+    /// Converts the guard into a plain reference for the rest of its lifetime.
+    pub fn into_mut(this: Self) -> &'a mut T {
+        this.value
+    }
+}
+
+impl<T: ?Sized> Deref for ArcMutGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T: ?Sized> DerefMut for ArcMutGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for ArcMutGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// Like [`get_mut_drop_weak`], but returns an [`ArcMutGuard`].
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_guard<T, P: SharedPointer<T>>(
+    ptr: &mut P,
+) -> Result<ArcMutGuard<'_, T>, &mut P> {
+    get_mut_drop_weak(ptr).map(|value| ArcMutGuard { value })
+}
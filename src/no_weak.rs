@@ -0,0 +1,96 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{Ordering, fence};
+use core::{fmt, ops::Deref};
+
+use crate::pointer::arc_get_mut_unchecked;
+
+/// An [`Arc`] that can't be downgraded.
+///
+/// Without weak pointers there is nothing to orphan, so
+/// [`NoWeakArc::get_mut`] only has to check the strong count and never needs
+/// the replacement machinery of [`get_mut_drop_weak`](crate::get_mut_drop_weak).
+///
+/// Every strong pointer to the value must be a `NoWeakArc`, or a plain `Arc`
+/// clone could create weak pointers behind its back. That's why
+/// [`NoWeakArc::from_arc`] only accepts an `Arc` that is the only pointer to
+/// its value, and [`NoWeakArc::into_arc`] only gives one back once it is.
+pub struct NoWeakArc<T>(Arc<T>);
+
+impl<T> NoWeakArc<T> {
+    /// Allocates `value` in a new `Arc`.
+    pub fn new(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// Wraps `arc` if it is the only strong pointer and there are no weak
+    /// ones, and otherwise returns it.
+    pub fn from_arc(arc: Arc<T>) -> Result<Self, Arc<T>> {
+        let mut arc = arc;
+        if Arc::get_mut(&mut arc).is_some() {
+            Ok(Self(arc))
+        } else {
+            Err(arc)
+        }
+    }
+
+    /// Converts back into a plain `Arc`, which can be downgraded again, if
+    /// this is the only pointer to the value, and otherwise returns it.
+    pub fn into_arc(this: Self) -> Result<Arc<T>, Self> {
+        let mut this = this;
+        if Arc::get_mut(&mut this.0).is_some() {
+            Ok(this.0)
+        } else {
+            Err(this)
+        }
+    }
+
+    /// Like [`Arc::get_mut`], but only checks the strong count.
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        if Arc::strong_count(&this.0) != 1 {
+            return None;
+        }
+        // Synchronize with the drops of other strong pointers.
+        fence(Ordering::Acquire);
+        // SAFETY: This is the only strong pointer, and there are no weak
+        // pointers that could be upgraded.
+        Some(unsafe { arc_get_mut_unchecked(&mut this.0) })
+    }
+
+    /// The number of strong pointers to the value.
+    pub fn strong_count(this: &Self) -> usize {
+        Arc::strong_count(&this.0)
+    }
+
+    /// Whether both pointers point to the same allocation.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+}
+
+impl<T> TryFrom<NoWeakArc<T>> for Arc<T> {
+    type Error = NoWeakArc<T>;
+
+    fn try_from(arc: NoWeakArc<T>) -> Result<Self, NoWeakArc<T>> {
+        NoWeakArc::into_arc(arc)
+    }
+}
+
+impl<T> Clone for NoWeakArc<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for NoWeakArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> Deref for NoWeakArc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
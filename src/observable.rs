@@ -0,0 +1,100 @@
+use alloc::{
+    boxed::Box,
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{fmt, ops::Deref};
+
+use crate::{Access, get_mut_drop_weak_access};
+
+/// An [`Arc`] whose replacements are announced to subscribers.
+///
+/// [`ObservableArc::get_mut_drop_weak`] orphans weak pointers like
+/// [`get_mut_drop_weak`](crate::get_mut_drop_weak), and then calls every
+/// callback registered with [`ObservableArc::subscribe`], so that holders of
+/// the now-dead weak pointers (or of anything keyed by the old address) know
+/// to re-resolve them. To deliver the notification elsewhere, subscribe a
+/// closure that sends on a channel.
+///
+/// Callbacks are not called if the allocation was kept, i.e. if there were no
+/// weak pointers to orphan.
+pub struct ObservableArc<T> {
+    arc: Arc<T>,
+    subscribers: Vec<Box<dyn FnMut() + Send + Sync>>,
+}
+
+impl<T> ObservableArc<T> {
+    /// Allocates `value` in a new `Arc` with no subscribers.
+    pub fn new(value: T) -> Self {
+        Self::from_arc(Arc::new(value))
+    }
+
+    /// Wraps `arc`, with no subscribers.
+    pub fn from_arc(arc: Arc<T>) -> Self {
+        Self {
+            arc,
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Unwraps the `Arc`, dropping the subscribers.
+    pub fn into_arc(this: Self) -> Arc<T> {
+        this.arc
+    }
+
+    /// The wrapped `Arc`, e.g. to clone it.
+    pub fn as_arc(this: &Self) -> &Arc<T> {
+        &this.arc
+    }
+
+    /// Creates a weak pointer to the current allocation.
+    pub fn downgrade(this: &Self) -> Weak<T> {
+        Arc::downgrade(&this.arc)
+    }
+
+    /// Registers `callback` to be called after each replacement.
+    pub fn subscribe(&mut self, callback: impl FnMut() + Send + Sync + 'static) {
+        self.subscribers.push(Box::new(callback));
+    }
+
+    /// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but notifies the
+    /// subscribers if the allocation was replaced.
+    ///
+    /// Returns None if the strong count was greater than 1 or a concurrent
+    /// weak upgrade won the race.
+    pub fn get_mut_drop_weak(&mut self) -> Option<&mut T> {
+        match get_mut_drop_weak_access(&mut self.arc) {
+            Access::Unchanged(value) => Some(value),
+            Access::Replaced(value) => {
+                for subscriber in &mut self.subscribers {
+                    subscriber();
+                }
+                Some(value)
+            }
+            Access::Shared(_) => None,
+        }
+    }
+}
+
+impl<T> From<Arc<T>> for ObservableArc<T> {
+    fn from(arc: Arc<T>) -> Self {
+        ObservableArc::from_arc(arc)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ObservableArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservableArc")
+            .field("value", &**self)
+            .field("subscribers", &self.subscribers.len())
+            .finish()
+    }
+}
+
+impl<T> Deref for ObservableArc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.arc
+    }
+}
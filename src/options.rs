@@ -0,0 +1,145 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+use crate::{SharedPointer, fallback::insert, get_mut_drop_weak_retry, instrument, unique_or_else};
+
+/// Runtime configuration for [`get_mut_with`], e.g. loaded from a config
+/// file, in place of picking one of the crate's functions at compile time.
+///
+/// The default behaves like [`get_mut_drop_weak`](crate::get_mut_drop_weak).
+///
+/// ```
+/// use std::sync::Arc;
+/// use get_mut_drop_weak::{GetMutOptions, get_mut_with};
+///
+/// let options = GetMutOptions::new().max_retries(3).allow_clone();
+///
+/// let mut arc = Arc::new(vec![1]);
+/// let other = Arc::clone(&arc);
+/// get_mut_with(&mut arc, &options).unwrap().push(2);
+/// assert_eq!(*other, [1]);
+/// ```
+pub struct GetMutOptions<T> {
+    replace: bool,
+    clone: Option<fn(&T) -> T>,
+    max_retries: usize,
+    #[cfg(feature = "std")]
+    wait: Option<Duration>,
+}
+
+impl<T> GetMutOptions<T> {
+    /// The default options.
+    pub fn new() -> Self {
+        Self {
+            replace: true,
+            clone: None,
+            max_retries: 0,
+            #[cfg(feature = "std")]
+            wait: None,
+        }
+    }
+
+    /// Whether the value may be moved to a new allocation to orphan weak
+    /// pointers. Defaults to true. Without it, only a pointer that is already
+    /// exclusive, or a clone, is handed out.
+    pub fn allow_replace(mut self, allow: bool) -> Self {
+        self.replace = allow;
+        self
+    }
+
+    /// Clones the value into a new allocation for the caller if it can't be
+    /// made exclusive otherwise, as
+    /// [`make_mut_drop_weak`](crate::make_mut_drop_weak) does.
+    pub fn allow_clone(mut self) -> Self
+    where
+        T: Clone,
+    {
+        self.clone = Some(T::clone);
+        self
+    }
+
+    /// How many more times to attempt a replacement after losing the race to
+    /// a concurrent weak upgrade, as [`get_mut_drop_weak_retry`] does.
+    /// Defaults to 0.
+    pub fn max_retries(mut self, retries: usize) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// How long to wait for other strong pointers to be dropped before
+    /// falling back to a clone or giving up, as
+    /// [`get_mut_drop_weak_blocking`](crate::get_mut_drop_weak_blocking)
+    /// does. Only applies if replacement is allowed. Takes precedence over
+    /// [`max_retries`](Self::max_retries), as lost races are retried until
+    /// the wait is over.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn wait(mut self, timeout: Duration) -> Self {
+        self.wait = Some(timeout);
+        self
+    }
+}
+
+impl<T> Default for GetMutOptions<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for GetMutOptions<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GetMutOptions<T> {}
+
+impl<T> fmt::Debug for GetMutOptions<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("GetMutOptions");
+        debug
+            .field("allow_replace", &self.replace)
+            .field("allow_clone", &self.clone.is_some())
+            .field("max_retries", &self.max_retries);
+        #[cfg(feature = "std")]
+        debug.field("wait", &self.wait);
+        debug.finish()
+    }
+}
+
+/// Exclusive access to the value behind `ptr`, obtained as `options` allow.
+///
+/// Returns Err(&mut Arc<T>), leaving the pointer unchanged, if none of the
+/// allowed ways succeeded.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_with<'a, T, P: SharedPointer<T>>(
+    ptr: &'a mut P,
+    options: &GetMutOptions<T>,
+) -> Result<&'a mut T, &'a mut P> {
+    let attempts = options.max_retries.saturating_add(1);
+    let result = if !options.replace {
+        unique_or_else(ptr, Err)
+    } else {
+        #[cfg(feature = "std")]
+        match options.wait {
+            Some(timeout) => crate::get_mut_drop_weak_blocking(ptr, timeout),
+            None => get_mut_drop_weak_retry(ptr, attempts),
+        }
+        #[cfg(not(feature = "std"))]
+        get_mut_drop_weak_retry(ptr, attempts)
+    };
+    let ptr = match result {
+        Ok(value) => return Ok(value),
+        Err(ptr) => ptr,
+    };
+    match options.clone {
+        Some(clone) => {
+            instrument::cloned();
+            let value = clone(ptr);
+            Ok(insert(ptr, value))
+        }
+        None => Err(ptr),
+    }
+}
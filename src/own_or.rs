@@ -0,0 +1,49 @@
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+/// Exclusive access to the value in a lock or cell behind a shared pointer,
+/// with or without taking the lock.
+///
+/// This is the result of `lock_or_own` and its counterparts, such as
+/// [`rc::borrow_mut_or_own`](crate::rc::borrow_mut_or_own), which bypass the
+/// lock when the pointer can be made exclusive. `G` is the guard the lock
+/// hands out otherwise, e.g. a `MutexGuard`, and each of those functions has
+/// an alias naming it, such as `OwnOrLock`.
+pub enum OwnOr<'a, T: ?Sized, G> {
+    /// The pointer was made exclusive, so the lock was bypassed.
+    Own(&'a mut T),
+    /// The pointer is strongly shared, so the lock was taken (or, for a
+    /// `RefCell`, the value was borrowed).
+    Locked(G),
+}
+
+impl<T: ?Sized, G: DerefMut<Target = T>> Deref for OwnOr<'_, T, G> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            Self::Own(value) => value,
+            Self::Locked(guard) => guard,
+        }
+    }
+}
+
+impl<T: ?Sized, G: DerefMut<Target = T>> DerefMut for OwnOr<'_, T, G> {
+    fn deref_mut(&mut self) -> &mut T {
+        match self {
+            Self::Own(value) => value,
+            Self::Locked(guard) => guard,
+        }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug, G: Deref<Target = T>> fmt::Debug for OwnOr<'_, T, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Own(value) => f.debug_tuple("Own").field(value).finish(),
+            Self::Locked(guard) => f.debug_tuple("Locked").field(&&**guard).finish(),
+        }
+    }
+}
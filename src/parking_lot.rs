@@ -0,0 +1,44 @@
+//! [`lock_or_own`] and [`write_or_own`] for `parking_lot` locks, which skip
+//! the lock when the [`Arc`] holding it can be made exclusive.
+//!
+//! `parking_lot` locks aren't poisoned, so unlike their `std` counterparts
+//! [`crate::lock_or_own`] and [`crate::write_or_own`], these can't fail.
+//!
+//! Requires the `parking_lot` feature.
+
+use alloc::sync::Arc;
+
+use parking_lot::{Mutex, MutexGuard, RwLock, RwLockWriteGuard};
+
+use crate::{OwnOr, get_mut_drop_weak};
+
+/// The result of [`lock_or_own`]: exclusive access to the value in a
+/// `Mutex`, with or without the lock.
+pub type OwnOrLock<'a, T> = OwnOr<'a, T, MutexGuard<'a, T>>;
+
+/// The result of [`write_or_own`]: exclusive access to the value in an
+/// `RwLock`, with or without the lock.
+pub type OwnOrWrite<'a, T> = OwnOr<'a, T, RwLockWriteGuard<'a, T>>;
+
+/// Exclusive access to the value in `arc`'s `Mutex`, skipping the lock when
+/// the `Arc` can be made exclusive with
+/// [`get_mut_drop_weak`](crate::get_mut_drop_weak), orphaning weak pointers
+/// as needed, and locking it otherwise.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn lock_or_own<T>(arc: &mut Arc<Mutex<T>>) -> OwnOrLock<'_, T> {
+    match get_mut_drop_weak(arc) {
+        Ok(mutex) => OwnOrLock::Own(mutex.get_mut()),
+        Err(arc) => OwnOrLock::Locked(arc.lock()),
+    }
+}
+
+/// Like [`lock_or_own`] for an `RwLock`: exclusive access to its value,
+/// skipping the lock when the `Arc` can be made exclusive, and taking the
+/// write lock otherwise.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn write_or_own<T>(arc: &mut Arc<RwLock<T>>) -> OwnOrWrite<'_, T> {
+    match get_mut_drop_weak(arc) {
+        Ok(lock) => OwnOrWrite::Own(lock.get_mut()),
+        Err(arc) => OwnOrWrite::Locked(arc.write()),
+    }
+}
@@ -0,0 +1,40 @@
+use core::{pin::Pin, ptr};
+
+use crate::SharedPointer;
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but for pinned
+/// pointers.
+///
+/// Replacing the pointer moves the value to a new allocation, which is fine
+/// because `T: Unpin`. For `!Unpin` types, see
+/// [`get_mut_drop_weak_pinned_unchecked`].
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_pinned<T: Unpin, P: SharedPointer<T>>(
+    pinned: &mut Pin<P>,
+) -> Result<Pin<&mut T>, &mut Pin<P>> {
+    // SAFETY: `T: Unpin`, so moving the value is allowed.
+    unsafe { get_mut_drop_weak_pinned_unchecked(pinned) }
+}
+
+/// Like [`get_mut_drop_weak_pinned`], but without requiring `T: Unpin`.
+///
+/// # Safety
+/// If the pointer has weak references, the value is moved to a new allocation
+/// even though it is pinned. The caller must ensure that nothing relies on the
+/// address of the pinned value (e.g. it has not been polled yet, or it holds
+/// no self-references), so that the move is harmless.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub unsafe fn get_mut_drop_weak_pinned_unchecked<T, P: SharedPointer<T>>(
+    pinned: &mut Pin<P>,
+) -> Result<Pin<&mut T>, &mut Pin<P>> {
+    // SAFETY: `Pin` is `repr(transparent)`. The pointer itself is never
+    // exposed to the caller unpinned; only the pointee may be moved, which
+    // the caller allows.
+    let ptr = unsafe { &mut *ptr::from_mut(pinned).cast::<P>() };
+    match crate::get_mut_drop_weak(ptr) {
+        // SAFETY: Either the value did not move, or the caller allows the move
+        // that just happened. From here on it stays put.
+        Ok(value) => Ok(unsafe { Pin::new_unchecked(value) }),
+        Err(ptr) => Err(unsafe { &mut *ptr::from_mut(ptr).cast::<Pin<P>>() }),
+    }
+}
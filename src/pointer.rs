@@ -0,0 +1,197 @@
+#[cfg(not(feature = "nightly-allocator"))]
+use alloc::{rc::Rc, sync::Arc};
+
+/// Implements [`SharedPointer`] for a pointer type with the same associated
+/// functions as [`Arc`](alloc::sync::Arc) (`get_mut`, `try_unwrap`, `new_uninit`, ...).
+///
+/// Pointer types without weak pointers pass `no_weak` to report a weak count
+/// of 0.
+macro_rules! impl_shared_pointer {
+    ($ptr:ident) => {
+        impl_shared_pointer!($ptr, |this| $ptr::weak_count(this));
+    };
+    ($ptr:ident, no_weak) => {
+        impl_shared_pointer!($ptr, |_this| 0);
+    };
+    ($ptr:ident, |$this:ident| $weak_count:expr) => {
+        impl<T> $crate::pointer::SharedPointer<T> for $ptr<T> {}
+
+        impl<T> $crate::pointer::sealed::Sealed<T> for $ptr<T> {
+            type Uninit = $ptr<::core::mem::MaybeUninit<T>>;
+
+            fn as_ptr(this: &Self) -> *const T {
+                $ptr::as_ptr(this)
+            }
+
+            fn strong_count(this: &Self) -> usize {
+                $ptr::strong_count(this)
+            }
+
+            fn weak_count($this: &Self) -> usize {
+                $weak_count
+            }
+
+            fn get_mut(this: &mut Self) -> Option<&mut T> {
+                $ptr::get_mut(this)
+            }
+
+            fn try_unwrap(this: Self) -> Result<T, Self> {
+                $ptr::try_unwrap(this)
+            }
+
+            fn new_like(_this: &Self, value: T) -> Self {
+                $ptr::new(value)
+            }
+
+            fn new_uninit(_this: &Self) -> Self::Uninit {
+                $ptr::new_uninit()
+            }
+
+            unsafe fn init(mut uninit: Self::Uninit, value: T) -> Self {
+                unsafe {
+                    $crate::pointer::sealed::Sealed::get_mut_unchecked(&mut uninit).write(value);
+                    uninit.assume_init()
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "nightly-allocator")]
+mod allocator_api;
+#[cfg(feature = "hybrid-rc")]
+mod hybrid_rc;
+#[cfg(loom)]
+mod loom;
+#[cfg(feature = "portable-atomic-util")]
+mod portable_atomic_util;
+#[cfg(feature = "rclite")]
+mod rclite;
+
+/// A reference-counted pointer type, typically supporting weak references, such as
+/// [`Arc`](alloc::sync::Arc) or [`Rc`](alloc::rc::Rc).
+///
+/// With the `nightly-allocator` feature (nightly only), `Arc<T, A>` and
+/// `Rc<T, A>` are supported for any `A: Allocator + Clone`, and replacements
+/// are allocated in the original pointer's allocator.
+///
+/// With the `portable-atomic-util` feature, this is also implemented for
+/// `portable_atomic_util::Arc`, for targets without native atomic
+/// read-modify-write operations. With the `hybrid-rc` feature, it is
+/// implemented for `hybrid_rc::Arc` and `hybrid_rc::Rc`. With the `rclite`
+/// feature, it is implemented for `rclite::Arc` and `rclite::Rc`; these have
+/// no weak pointers, so they are never replaced.
+///
+/// When compiled with `--cfg loom`, it is implemented for `loom::sync::Arc`
+/// so that loom models can call into this crate. `shuttle::sync::Arc` is a
+/// re-export of the std `Arc`, so shuttle tests need no special support.
+///
+/// This trait is sealed and cannot be implemented outside of this crate. It
+/// exists so that [`get_mut_drop_weak`](crate::get_mut_drop_weak) can be
+/// called from code that is generic over the pointer type.
+pub trait SharedPointer<T>: sealed::Sealed<T> {}
+
+pub(crate) mod sealed {
+    use core::alloc::Layout;
+    use core::ops::Deref;
+
+    use crate::AllocFailed;
+
+    /// The operations the replacement algorithm needs from a pointer type.
+    pub trait Sealed<T>: Sized + Deref<Target = T> {
+        /// Storage for a not-yet-initialized replacement pointer.
+        type Uninit;
+
+        fn as_ptr(this: &Self) -> *const T;
+        fn strong_count(this: &Self) -> usize;
+        fn weak_count(this: &Self) -> usize;
+        fn get_mut(this: &mut Self) -> Option<&mut T>;
+        fn try_unwrap(this: Self) -> Result<T, Self>;
+        /// Allocates a new pointer to `value`, alongside `this`.
+        fn new_like(this: &Self, value: T) -> Self;
+        /// Allocates storage for a replacement of `this`.
+        fn new_uninit(this: &Self) -> Self::Uninit;
+
+        /// Like [`Sealed::new_uninit`], but reports allocation failure.
+        ///
+        /// Stable pointer types have no fallible constructor, so by default
+        /// this probes the global allocator for a block of the size the
+        /// replacement needs and releases it before calling `new_uninit`.
+        /// That catches sizes the allocator can't satisfy, but another thread
+        /// may still exhaust memory in between.
+        fn try_new_uninit(this: &Self) -> Result<Self::Uninit, AllocFailed> {
+            // The allocation holds the two counts followed by the value.
+            let (layout, _) = Layout::new::<[usize; 2]>()
+                .extend(Layout::new::<T>())
+                .map_err(|_| AllocFailed)?;
+            let layout = layout.pad_to_align();
+            // SAFETY: `layout` has a non-zero size.
+            let probe = unsafe { alloc::alloc::alloc(layout) };
+            if probe.is_null() {
+                return Err(AllocFailed);
+            }
+            // SAFETY: `probe` was just allocated with `layout`.
+            unsafe { alloc::alloc::dealloc(probe, layout) };
+            Ok(Self::new_uninit(this))
+        }
+
+        /// # Safety
+        /// `uninit` must have been created by [`Sealed::new_uninit`] or
+        /// [`Sealed::try_new_uninit`] and must
+        /// not have been shared since.
+        unsafe fn init(uninit: Self::Uninit, value: T) -> Self;
+
+        /// Whether `uninit`, allocated for another pointer, can replace `this`
+        /// without moving the value to a different allocator.
+        ///
+        /// Pointer types in the global allocator can always share storage.
+        fn can_reuse(this: &Self, uninit: &Self::Uninit) -> bool {
+            let _ = (this, uninit);
+            true
+        }
+
+        /// Whether `this` is the only strong pointer and there are no weak ones.
+        fn is_unique(this: &mut Self) -> bool {
+            Self::get_mut(this).is_some()
+        }
+
+        /// Use [`Arc::get_mut_unchecked`](alloc::sync::Arc::get_mut_unchecked)
+        /// and [`Rc::get_mut_unchecked`](alloc::rc::Rc::get_mut_unchecked) when stable.
+        ///
+        /// ```compile_fail
+        /// use std::sync::Arc;
+        /// let mut a = Arc::new(0usize);
+        /// let b = unsafe { Arc::get_mut_unchecked(&mut a) };
+        /// *b += 1;
+        /// ```
+        ///
+        /// # Safety
+        /// The caller must have established that `this` is the only strong
+        /// pointer and that no weak pointer can be upgraded while the returned
+        /// reference is alive.
+        unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T {
+            unsafe { &mut *Self::as_ptr(this).cast_mut() }
+        }
+    }
+}
+
+/// [`Sealed::get_mut_unchecked`] for unsized payloads, which `Sealed` doesn't
+/// cover.
+///
+/// # Safety
+/// Same as [`Sealed::get_mut_unchecked`].
+pub(crate) unsafe fn arc_get_mut_unchecked<U: ?Sized>(arc: &mut alloc::sync::Arc<U>) -> &mut U {
+    #[cfg(feature = "nightly")]
+    unsafe {
+        alloc::sync::Arc::get_mut_unchecked(arc)
+    }
+    #[cfg(not(feature = "nightly"))]
+    unsafe {
+        &mut *alloc::sync::Arc::as_ptr(arc).cast_mut()
+    }
+}
+
+#[cfg(not(feature = "nightly-allocator"))]
+impl_shared_pointer!(Arc);
+#[cfg(not(feature = "nightly-allocator"))]
+impl_shared_pointer!(Rc);
@@ -0,0 +1,70 @@
+use alloc::{rc::Rc, sync::Arc};
+use core::alloc::Allocator;
+use core::mem::{self, MaybeUninit};
+
+use super::{SharedPointer, sealed::Sealed};
+use crate::AllocFailed;
+
+macro_rules! impl_shared_pointer_in {
+    ($ptr:ident) => {
+        impl<T, A: Allocator + Clone> SharedPointer<T> for $ptr<T, A> {}
+
+        impl<T, A: Allocator + Clone> Sealed<T> for $ptr<T, A> {
+            type Uninit = $ptr<MaybeUninit<T>, A>;
+
+            fn as_ptr(this: &Self) -> *const T {
+                $ptr::as_ptr(this)
+            }
+
+            fn strong_count(this: &Self) -> usize {
+                $ptr::strong_count(this)
+            }
+
+            fn weak_count(this: &Self) -> usize {
+                $ptr::weak_count(this)
+            }
+
+            fn get_mut(this: &mut Self) -> Option<&mut T> {
+                $ptr::get_mut(this)
+            }
+
+            fn try_unwrap(this: Self) -> Result<T, Self> {
+                $ptr::try_unwrap(this)
+            }
+
+            fn new_like(this: &Self, value: T) -> Self {
+                $ptr::new_in(value, $ptr::allocator(this).clone())
+            }
+
+            fn new_uninit(this: &Self) -> Self::Uninit {
+                // Allocate the replacement alongside the original.
+                $ptr::new_uninit_in($ptr::allocator(this).clone())
+            }
+
+            fn can_reuse(_this: &Self, _uninit: &Self::Uninit) -> bool {
+                // Allocators can't be compared, but all values of a stateless
+                // allocator are interchangeable.
+                mem::size_of::<A>() == 0
+            }
+
+            fn try_new_uninit(this: &Self) -> Result<Self::Uninit, AllocFailed> {
+                $ptr::try_new_uninit_in($ptr::allocator(this).clone()).map_err(|_| AllocFailed)
+            }
+
+            #[cfg(feature = "nightly")]
+            unsafe fn get_mut_unchecked(this: &mut Self) -> &mut T {
+                unsafe { $ptr::get_mut_unchecked(this) }
+            }
+
+            unsafe fn init(mut uninit: Self::Uninit, value: T) -> Self {
+                unsafe {
+                    Sealed::get_mut_unchecked(&mut uninit).write(value);
+                    uninit.assume_init()
+                }
+            }
+        }
+    };
+}
+
+impl_shared_pointer_in!(Arc);
+impl_shared_pointer_in!(Rc);
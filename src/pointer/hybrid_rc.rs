@@ -0,0 +1,6 @@
+use hybrid_rc::{Arc, Rc};
+
+// `HybridRc`'s state parameter is bounded by a trait we can't name, so each
+// state gets its own impl.
+impl_shared_pointer!(Arc);
+impl_shared_pointer!(Rc);
@@ -0,0 +1,47 @@
+use core::mem::MaybeUninit;
+
+use loom::sync::Arc;
+
+use super::{SharedPointer, sealed::Sealed};
+
+// `loom::sync::Arc` has no `new_uninit`, so the replacement is preallocated as
+// a std `Arc` and handed to loom with `Arc::from_std` once it is initialized.
+// loom 0.7 has no `Weak`, so in practice the replacement path is unreachable
+// and models only explore the strong-count checks.
+impl<T> SharedPointer<T> for Arc<T> {}
+
+impl<T> Sealed<T> for Arc<T> {
+    type Uninit = alloc::sync::Arc<MaybeUninit<T>>;
+
+    fn as_ptr(this: &Self) -> *const T {
+        Arc::as_ptr(this)
+    }
+
+    fn strong_count(this: &Self) -> usize {
+        Arc::strong_count(this)
+    }
+
+    fn weak_count(_this: &Self) -> usize {
+        0
+    }
+
+    fn get_mut(this: &mut Self) -> Option<&mut T> {
+        Arc::get_mut(this)
+    }
+
+    fn try_unwrap(this: Self) -> Result<T, Self> {
+        Arc::try_unwrap(this)
+    }
+
+    fn new_like(_this: &Self, value: T) -> Self {
+        Arc::new(value)
+    }
+
+    fn new_uninit(_this: &Self) -> Self::Uninit {
+        alloc::sync::Arc::new_uninit()
+    }
+
+    unsafe fn init(uninit: Self::Uninit, value: T) -> Self {
+        Arc::from_std(unsafe { Sealed::init(uninit, value) })
+    }
+}
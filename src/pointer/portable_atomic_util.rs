@@ -0,0 +1,3 @@
+use portable_atomic_util::Arc;
+
+impl_shared_pointer!(Arc);
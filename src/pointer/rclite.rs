@@ -0,0 +1,4 @@
+use rclite::{Arc, Rc};
+
+impl_shared_pointer!(Arc, no_weak);
+impl_shared_pointer!(Rc, no_weak);
@@ -0,0 +1,169 @@
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+use crate::{
+    Exclusivity, FailureReason, SharedPointer, exclusivity, fallback::insert,
+    get_mut_drop_weak_detailed, instrument,
+};
+
+/// What [`get_mut_with_policy`] should do about a pointer that isn't
+/// exclusive, as decided by a [`ReclaimPolicy`].
+///
+/// Variants may be added by features, such as `Wait` with `std`,
+/// so matches on it need a wildcard arm.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum Reclaim<T> {
+    /// Move the value to a new allocation, orphaning weak pointers, as
+    /// [`get_mut_drop_weak`](crate::get_mut_drop_weak) does. Fails if the
+    /// pointer is strongly shared.
+    Replace,
+    /// Point the caller's pointer at a new allocation holding a copy of the
+    /// value made by the function, leaving other strong pointers with the
+    /// original, as [`make_mut_drop_weak`](crate::make_mut_drop_weak) does.
+    Clone(fn(&T) -> T),
+    /// Wait up to the given time for other strong pointers to be dropped, as
+    /// [`get_mut_drop_weak_blocking`](crate::get_mut_drop_weak_blocking)
+    /// does. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    Wait(Duration),
+    /// Give up, leaving the pointer unchanged.
+    Fail,
+}
+
+/// Decides how [`get_mut_with_policy`] reclaims a pointer that isn't
+/// exclusive, so that code paths can be parameterized by a policy type.
+///
+/// ```
+/// use std::sync::Arc;
+/// use get_mut_drop_weak::{Exclusivity, Reclaim, ReclaimPolicy, get_mut_with_policy};
+///
+/// /// Clones small strongly shared buffers, and gives up on the rest.
+/// struct CloneSmall;
+///
+/// impl ReclaimPolicy<Vec<u8>> for CloneSmall {
+///     fn decide(&self, value: &Vec<u8>, state: Exclusivity) -> Reclaim<Vec<u8>> {
+///         match state {
+///             Exclusivity::Shared { .. } if value.len() <= 64 => Reclaim::Clone(Vec::clone),
+///             Exclusivity::Shared { .. } => Reclaim::Fail,
+///             _ => Reclaim::Replace,
+///         }
+///     }
+/// }
+///
+/// let mut arc = Arc::new(vec![1]);
+/// let other = Arc::clone(&arc);
+/// get_mut_with_policy(&mut arc, &CloneSmall).unwrap().push(2);
+/// assert_eq!(*arc, [1, 2]);
+/// assert_eq!(*other, [1]);
+/// ```
+pub trait ReclaimPolicy<T> {
+    /// Decides what to do about `value`, whose pointer is in `state`, which
+    /// is never [`Exclusivity::Unique`].
+    fn decide(&self, value: &T, state: Exclusivity) -> Reclaim<T>;
+}
+
+/// Orphans weak pointers, and fails if the pointer is strongly shared: the
+/// behavior of [`get_mut_drop_weak`](crate::get_mut_drop_weak).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ReplaceWeak;
+
+impl<T> ReclaimPolicy<T> for ReplaceWeak {
+    fn decide(&self, _value: &T, _state: Exclusivity) -> Reclaim<T> {
+        Reclaim::Replace
+    }
+}
+
+/// Orphans weak pointers, and clones the value if the pointer is strongly
+/// shared: the behavior of
+/// [`make_mut_drop_weak`](crate::make_mut_drop_weak).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ReplaceOrClone;
+
+impl<T: Clone> ReclaimPolicy<T> for ReplaceOrClone {
+    fn decide(&self, _value: &T, state: Exclusivity) -> Reclaim<T> {
+        match state {
+            Exclusivity::Shared { .. } => Reclaim::Clone(T::clone),
+            _ => Reclaim::Replace,
+        }
+    }
+}
+
+/// Clones values of types smaller than the given number of bytes, and
+/// orphans the weak pointers of larger ones, cloning those only if the
+/// pointer is strongly shared.
+///
+/// Both ways end with the caller's pointer at a new allocation, but a
+/// replacement moves the value, which is a `memcpy` of the whole type, while
+/// a clone of a small `Copy`-like type may be cheaper than preparing the
+/// replacement. The size is `size_of::<T>()`, not counting any heap data `T`
+/// owns, which a clone would copy too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CloneBelow(pub usize);
+
+impl<T: Clone> ReclaimPolicy<T> for CloneBelow {
+    fn decide(&self, _value: &T, state: Exclusivity) -> Reclaim<T> {
+        match state {
+            Exclusivity::WeakOnly { .. } if size_of::<T>() >= self.0 => Reclaim::Replace,
+            _ => Reclaim::Clone(T::clone),
+        }
+    }
+}
+
+/// Orphans weak pointers, and waits up to the given time if the pointer is
+/// strongly shared. Requires the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReplaceOrWait(pub Duration);
+
+#[cfg(feature = "std")]
+impl<T> ReclaimPolicy<T> for ReplaceOrWait {
+    fn decide(&self, _value: &T, state: Exclusivity) -> Reclaim<T> {
+        match state {
+            Exclusivity::Shared { .. } => Reclaim::Wait(self.0),
+            _ => Reclaim::Replace,
+        }
+    }
+}
+
+/// Exclusive access to the value behind `ptr`, reclaiming it as `policy`
+/// decides if it isn't exclusive already.
+///
+/// If a concurrent weak upgrade wins the race against a replacement, the
+/// policy is consulted once more with the new state.
+///
+/// Returns Err(&mut Arc<T>), leaving the pointer unchanged, if the policy
+/// gives up, or its choice fails.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_with_policy<'a, T, P: SharedPointer<T>>(
+    ptr: &'a mut P,
+    policy: &impl ReclaimPolicy<T>,
+) -> Result<&'a mut T, &'a mut P> {
+    let mut ptr = ptr;
+    let mut raced = false;
+    loop {
+        let state = exclusivity(ptr);
+        let reclaim = match state {
+            Exclusivity::Unique => Reclaim::Replace,
+            _ => policy.decide(ptr, state),
+        };
+        match reclaim {
+            Reclaim::Replace => match get_mut_drop_weak_detailed(ptr) {
+                Ok(value) => return Ok(value),
+                Err(err) if !raced && err.reason() == FailureReason::LostRace => {
+                    raced = true;
+                    ptr = err.into_inner();
+                }
+                Err(err) => return Err(err.into_inner()),
+            },
+            Reclaim::Clone(clone) => {
+                instrument::cloned();
+                let value = clone(ptr);
+                return Ok(insert(ptr, value));
+            }
+            #[cfg(feature = "std")]
+            Reclaim::Wait(timeout) => return crate::get_mut_drop_weak_blocking(ptr, timeout),
+            Reclaim::Fail => return Err(ptr),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::{cell::UnsafeCell, fmt, mem::MaybeUninit};
+
+use crate::{guard, instrument, pointer::arc_get_mut_unchecked, spin::SpinLock};
+
+/// Preallocated replacement allocations for [`get_mut_drop_weak_pooled`].
+///
+/// Each replacement of a weakly shared `Arc` needs a new allocation. The pool
+/// keeps unshared `Arc<MaybeUninit<T>>` nodes around, refilling `batch` of
+/// them at a time when it runs dry, so that allocator traffic happens in
+/// bursts outside the hot path (or ahead of time, with [`ArcPool::reserve`])
+/// rather than on every replacement. Nodes that aren't used because a weak
+/// upgrade won the race go back to the pool.
+pub struct ArcPool<T> {
+    lock: SpinLock,
+    nodes: UnsafeCell<Vec<Arc<MaybeUninit<T>>>>,
+    batch: usize,
+}
+
+impl<T> ArcPool<T> {
+    /// Creates an empty pool that allocates `batch` nodes whenever it runs
+    /// out. A `batch` of 0 is treated as 1.
+    pub const fn new(batch: usize) -> Self {
+        Self {
+            lock: SpinLock::new(),
+            nodes: UnsafeCell::new(Vec::new()),
+            batch: if batch == 0 { 1 } else { batch },
+        }
+    }
+
+    /// Allocates nodes until at least `count` are available.
+    pub fn reserve(&self, count: usize) {
+        let missing = count.saturating_sub(self.len());
+        let fresh: Vec<_> = (0..missing).map(|_| Arc::new_uninit()).collect();
+        self.with_nodes(|nodes| nodes.extend(fresh));
+    }
+
+    /// The number of available nodes.
+    pub fn len(&self) -> usize {
+        self.with_nodes(|nodes| nodes.len())
+    }
+
+    /// Whether no nodes are available.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn take(&self) -> Arc<MaybeUninit<T>> {
+        if let Some(node) = self.with_nodes(Vec::pop) {
+            return node;
+        }
+        // Allocate outside the lock.
+        let mut fresh: Vec<_> = (0..self.batch).map(|_| Arc::new_uninit()).collect();
+        let node = fresh.pop().expect("batch is at least 1");
+        self.with_nodes(|nodes| nodes.extend(fresh));
+        node
+    }
+
+    fn with_nodes<R>(&self, f: impl FnOnce(&mut Vec<Arc<MaybeUninit<T>>>) -> R) -> R {
+        let _locked = self.lock.lock();
+        // SAFETY: The lock is held.
+        f(unsafe { &mut *self.nodes.get() })
+    }
+}
+
+// SAFETY: The nodes are only accessed under the lock. They are unshared and
+// hold no value, so handing them to another thread is like sending a `T`.
+unsafe impl<T: Send> Sync for ArcPool<T> {}
+
+impl<T> Default for ArcPool<T> {
+    fn default() -> Self {
+        Self::new(16)
+    }
+}
+
+impl<T> fmt::Debug for ArcPool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArcPool")
+            .field("len", &self.len())
+            .field("batch", &self.batch)
+            .finish()
+    }
+}
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but takes the
+/// replacement allocation from `pool`.
+///
+/// Returns Err(&mut Arc<T>) if the strong count was greater than 1 or a
+/// concurrent weak upgrade won the race.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_pooled<'a, T>(
+    arc: &'a mut Arc<T>,
+    pool: &ArcPool<T>,
+) -> Result<&'a mut T, &'a mut Arc<T>> {
+    if Arc::get_mut(arc).is_some() {
+        instrument::fast_path();
+    } else {
+        let strong = Arc::strong_count(arc);
+        if strong > 1 {
+            instrument::strongly_shared::<T>(strong, Arc::weak_count(arc));
+            return Err(arc);
+        }
+        // Strong = 1, Weak > 0. Replace with a node from the pool.
+        let orphaned = Arc::weak_count(arc);
+        let mut node = pool.take();
+        let replaced = guard::moved_out(arc, |original| match Arc::try_unwrap(original) {
+            Ok(value) => {
+                // SAFETY: Pool nodes are never shared.
+                unsafe { arc_get_mut_unchecked(&mut node) }.write(value);
+                // SAFETY: Just initialized.
+                (unsafe { node.assume_init() }, None)
+            }
+            Err(restored) => (restored, Some(node)),
+        });
+        if let Some(node) = replaced {
+            pool.with_nodes(|nodes| nodes.push(node));
+            instrument::lost_race::<T>(Arc::strong_count(arc), Arc::weak_count(arc));
+            return Err(arc);
+        }
+        instrument::replaced::<T>(orphaned);
+    }
+    // SAFETY: Either `Arc::get_mut` succeeded, or `arc` was just replaced by a
+    // pool node that nothing else can reach.
+    Ok(unsafe { arc_get_mut_unchecked(arc) })
+}
@@ -0,0 +1,163 @@
+use alloc::sync::{Arc, Weak};
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+use std::sync::{PoisonError, RwLock};
+
+use crate::get_mut_drop_weak;
+
+type Slot<T> = RwLock<Weak<T>>;
+
+/// An [`Arc`] with two kinds of weak handles: plain [`Weak`] pointers, which
+/// [`ProxyArc::get_mut_drop_weak`] orphans as usual, and [`ProxyWeak`]
+/// handles, which survive it.
+///
+/// Proxy handles don't point at the value directly but at a shared slot
+/// holding a weak pointer, which is re-pointed at the new allocation after
+/// each replacement. Upgrading one therefore takes a read lock.
+///
+/// Requires the `std` feature.
+pub struct ProxyArc<T> {
+    arc: Arc<T>,
+    slot: Arc<Slot<T>>,
+}
+
+/// A weak handle to a [`ProxyArc`] that keeps working across replacements.
+pub struct ProxyWeak<T> {
+    slot: Arc<Slot<T>>,
+}
+
+/// Exclusive access to the value of a [`ProxyArc`], from
+/// [`ProxyArc::get_mut_drop_weak`]. Proxy handles are reconnected to the value
+/// when this is dropped.
+pub struct ProxyMut<'a, T> {
+    arc: &'a mut Arc<T>,
+    slot: &'a Slot<T>,
+}
+
+impl<T> ProxyArc<T> {
+    /// Allocates `value` in a new `Arc`.
+    pub fn new(value: T) -> Self {
+        let arc = Arc::new(value);
+        let slot = Arc::new(RwLock::new(Arc::downgrade(&arc)));
+        Self { arc, slot }
+    }
+
+    /// Creates a plain weak pointer, which is orphaned by the next
+    /// replacement.
+    pub fn downgrade(this: &Self) -> Weak<T> {
+        Arc::downgrade(&this.arc)
+    }
+
+    /// Creates a proxy handle, which survives replacements.
+    pub fn proxy(this: &Self) -> ProxyWeak<T> {
+        ProxyWeak {
+            slot: Arc::clone(&this.slot),
+        }
+    }
+
+    /// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak): orphans plain
+    /// weak pointers, but keeps proxy handles.
+    ///
+    /// While the returned guard is alive, proxy handles fail to upgrade, as
+    /// they would for a plain `Arc` with an outstanding `&mut`. They point at
+    /// the value again once it is dropped.
+    ///
+    /// Returns None if the strong count was greater than 1 or a concurrent
+    /// weak upgrade won the race.
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    pub fn get_mut_drop_weak(&mut self) -> Option<ProxyMut<'_, T>> {
+        // Detach the proxies first, so their weak pointer doesn't force a
+        // replacement on its own.
+        *lock(&self.slot) = Weak::new();
+        if get_mut_drop_weak(&mut self.arc).is_err() {
+            *lock(&self.slot) = Arc::downgrade(&self.arc);
+            return None;
+        }
+        Some(ProxyMut {
+            arc: &mut self.arc,
+            slot: &self.slot,
+        })
+    }
+}
+
+impl<T> ProxyWeak<T> {
+    /// Attempts to upgrade to a strong pointer to the current allocation.
+    ///
+    /// Fails if the value was dropped, or while the [`ProxyArc`] is being
+    /// mutated.
+    pub fn upgrade(&self) -> Option<Arc<T>> {
+        self.slot
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .upgrade()
+    }
+}
+
+fn lock<T>(slot: &Slot<T>) -> std::sync::RwLockWriteGuard<'_, Weak<T>> {
+    slot.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl<T> Drop for ProxyMut<'_, T> {
+    fn drop(&mut self) {
+        *lock(self.slot) = Arc::downgrade(self.arc);
+    }
+}
+
+impl<T> Deref for ProxyMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.arc
+    }
+}
+
+impl<T> DerefMut for ProxyMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        Arc::get_mut(self.arc).expect("proxies are detached")
+    }
+}
+
+impl<T> Clone for ProxyArc<T> {
+    fn clone(&self) -> Self {
+        Self {
+            arc: Arc::clone(&self.arc),
+            slot: Arc::clone(&self.slot),
+        }
+    }
+}
+
+impl<T> Clone for ProxyWeak<T> {
+    fn clone(&self) -> Self {
+        Self {
+            slot: Arc::clone(&self.slot),
+        }
+    }
+}
+
+impl<T> Deref for ProxyArc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.arc
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ProxyArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> fmt::Debug for ProxyWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(ProxyWeak)")
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ProxyMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,68 @@
+use alloc::{collections::BTreeMap, collections::VecDeque, rc, sync, vec::Vec};
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Removes the weak pointers that can no longer be upgraded from a
+/// collection, e.g. after [`get_mut_drop_weak`](crate::get_mut_drop_weak)
+/// orphaned them.
+///
+/// Implemented for `Vec`, `VecDeque`, `BTreeMap` and (with the `std` feature)
+/// `HashMap` values of both [`sync::Weak`] and [`rc::Weak`].
+///
+/// ```
+/// use std::sync::Arc;
+/// use get_mut_drop_weak::PurgeDeadWeaks;
+///
+/// let live = Arc::new(1);
+/// let dead = Arc::new(2);
+/// let mut weaks = vec![Arc::downgrade(&live), Arc::downgrade(&dead)];
+/// drop(dead);
+/// assert_eq!(weaks.purge_dead_weaks(), 1);
+/// assert_eq!(weaks.len(), 1);
+/// ```
+pub trait PurgeDeadWeaks {
+    /// Removes the dead weak pointers and returns how many there were.
+    fn purge_dead_weaks(&mut self) -> usize;
+}
+
+macro_rules! impl_purge_dead_weaks {
+    ($weak:path) => {
+        impl<T: ?Sized> PurgeDeadWeaks for Vec<$weak> {
+            fn purge_dead_weaks(&mut self) -> usize {
+                let len = self.len();
+                self.retain(|weak| weak.strong_count() > 0);
+                len - self.len()
+            }
+        }
+
+        impl<T: ?Sized> PurgeDeadWeaks for VecDeque<$weak> {
+            fn purge_dead_weaks(&mut self) -> usize {
+                let len = self.len();
+                self.retain(|weak| weak.strong_count() > 0);
+                len - self.len()
+            }
+        }
+
+        impl<K: Ord, T: ?Sized> PurgeDeadWeaks for BTreeMap<K, $weak> {
+            fn purge_dead_weaks(&mut self) -> usize {
+                let len = self.len();
+                self.retain(|_, weak| weak.strong_count() > 0);
+                len - self.len()
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<K: Eq + Hash, T: ?Sized, S: BuildHasher> PurgeDeadWeaks for HashMap<K, $weak, S> {
+            fn purge_dead_weaks(&mut self) -> usize {
+                let len = self.len();
+                self.retain(|_, weak| weak.strong_count() > 0);
+                len - self.len()
+            }
+        }
+    };
+}
+
+impl_purge_dead_weaks!(sync::Weak<T>);
+impl_purge_dead_weaks!(rc::Weak<T>);
@@ -0,0 +1,41 @@
+use alloc::vec::Vec;
+use core::hash::{BuildHasher, Hash};
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+
+use crate::{BatchOutcome, ReplacementCache, SharedPointer, batch::outcome};
+
+/// [`get_mut_drop_weak_batch`](crate::get_mut_drop_weak_batch), spread
+/// across the rayon thread pool.
+///
+/// Each rayon job keeps its own replacement allocation for reuse after a
+/// lost race.
+///
+/// Requires the `rayon` feature.
+pub fn par_get_mut_drop_weak_batch<T, P: SharedPointer<T> + Send>(
+    ptrs: &mut [P],
+) -> Vec<BatchOutcome> {
+    ptrs.par_iter_mut()
+        .map_init(ReplacementCache::new, |cache, ptr| outcome(ptr, cache))
+        .collect()
+}
+
+/// [`make_unique_all_drop_weak`](crate::make_unique_all_drop_weak), spread
+/// across the rayon thread pool. The still-shared keys are returned in no
+/// particular order.
+///
+/// Requires the `rayon` feature.
+pub fn par_make_unique_all_drop_weak<K, T, P, S>(map: &mut HashMap<K, P, S>) -> Vec<K>
+where
+    K: Eq + Hash + Clone + Send + Sync,
+    P: SharedPointer<T> + Send,
+    S: BuildHasher + Sync,
+{
+    map.par_iter_mut()
+        .map_init(ReplacementCache::new, |cache, (key, ptr)| {
+            (outcome(ptr, cache) == BatchOutcome::Shared).then(|| key.clone())
+        })
+        .flatten()
+        .collect()
+}
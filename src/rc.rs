@@ -0,0 +1,81 @@
+//! Single-threaded counterparts of [`get_mut_drop_weak`](crate::get_mut_drop_weak)
+//! and of the lock helpers for [`Rc`].
+
+use alloc::rc::Rc;
+use core::cell::{RefCell, RefMut};
+use core::mem::MaybeUninit;
+
+use crate::{OwnOr, Sealed, guard::moved_out, instrument};
+
+/// Attempts to get a mutable reference to the inner data of an Rc.
+///
+/// If the Rc has a strong count of 1 and a weak count of 0, it returns
+/// the mutable reference directly.
+///
+/// If the Rc has a strong count greater than 1, it returns Err.
+///
+/// If the Rc has a strong count of 1 and a weak count greater than 0,
+/// it replaces the Rc instance with a new one containing the same data,
+/// invalidating all existing weak pointers. This involves an internal
+/// allocation for the new Rc instance. If this allocation fails, the function
+/// will panic (before modifying the input Rc).
+///
+/// Unlike the [`Arc`](alloc::sync::Arc) version, the replacement can never fail
+/// once started: `Rc` is `!Send`, so no other thread can upgrade a weak
+/// pointer while the value is being moved.
+///
+/// Returns Ok(&mut T) on success, or Err(&mut Rc<T>) if the strong count was
+/// greater than 1. See [`crate::get_mut_drop_weak`] for why the Err variant
+/// hands back the reference.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak<T>(rc: &mut Rc<T>) -> Result<&mut T, &mut Rc<T>> {
+    if Rc::get_mut(rc).is_some() {
+        // Strong=1, Weak=0. Already exclusive.
+        instrument::fast_path();
+        return Ok(unsafe { Sealed::get_mut_unchecked(rc) });
+    }
+    let strong = Rc::strong_count(rc);
+    if strong > 1 {
+        instrument::strongly_shared::<T>(strong, Rc::weak_count(rc));
+        return Err(rc);
+    }
+
+    // State: Strong = 1, Weak > 0. Need to replace the Rc instance.
+
+    // Allocate first so that an allocation failure panics before `rc` is touched.
+    let mut preallocated_rc: Rc<MaybeUninit<T>> = Rc::new_uninit();
+    let orphaned = Rc::weak_count(rc);
+
+    // SAFETY: The strong count is 1 and `Rc` is `!Send`, so nothing can
+    // upgrade a weak pointer between the check above and this unwrap.
+    unsafe {
+        moved_out(rc, |original_rc| {
+            let value = Rc::try_unwrap(original_rc).unwrap_unchecked();
+            Sealed::get_mut_unchecked(&mut preallocated_rc).write(value);
+            (preallocated_rc.assume_init(), ())
+        });
+        instrument::replaced::<T>(orphaned);
+        Ok(Sealed::get_mut_unchecked(rc))
+    }
+}
+
+/// The result of [`borrow_mut_or_own`]: exclusive access to the value in a
+/// `RefCell`, with or without a runtime borrow.
+pub type OwnOrBorrow<'a, T> = OwnOr<'a, T, RefMut<'a, T>>;
+
+/// Exclusive access to the value in `rc`'s `RefCell`, skipping the runtime
+/// borrow check when the `Rc` can be made exclusive with
+/// [`get_mut_drop_weak`], orphaning weak pointers as needed, and calling
+/// [`RefCell::borrow_mut`] otherwise.
+///
+/// # Panics
+///
+/// Panics if the `Rc` is strongly shared and the value is currently
+/// borrowed, as `borrow_mut` does.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn borrow_mut_or_own<T>(rc: &mut Rc<RefCell<T>>) -> OwnOrBorrow<'_, T> {
+    match get_mut_drop_weak(rc) {
+        Ok(cell) => OwnOrBorrow::Own(cell.get_mut()),
+        Err(rc) => OwnOrBorrow::Locked(rc.borrow_mut()),
+    }
+}
@@ -0,0 +1,147 @@
+use alloc::{
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{fmt, ops::Deref, panic::Location};
+
+use crate::{Access, get_mut_drop_weak_access};
+
+type Origin = &'static Location<'static>;
+
+/// An [`Arc`] that records where each of its weak pointers was created.
+///
+/// Weak handles are [`RegisteredWeak`]s, created with
+/// [`WeakRegistry::downgrade`], which records the caller's source location.
+/// [`WeakRegistry::origins`] lists the locations of the handles that are
+/// still alive; a handle's record goes away with its last clone.
+///
+/// The `Arc` itself is never handed out, so every weak pointer to it goes
+/// through the registry. A handle can still be upgraded and the resulting
+/// `Arc` downgraded directly; such weak pointers are counted by
+/// [`WeakRegistry::live_weaks`] but have no recorded origin.
+pub struct WeakRegistry<T> {
+    arc: Arc<T>,
+    origins: Vec<Weak<Origin>>,
+}
+
+/// A weak handle to a [`WeakRegistry`], carrying the location of its
+/// creation.
+pub struct RegisteredWeak<T> {
+    weak: Weak<T>,
+    origin: Arc<Origin>,
+}
+
+impl<T> WeakRegistry<T> {
+    /// Allocates `value` in a new `Arc`.
+    pub fn new(value: T) -> Self {
+        Self {
+            arc: Arc::new(value),
+            origins: Vec::new(),
+        }
+    }
+
+    /// Wraps `arc` if it is the only pointer to its value, and otherwise
+    /// returns it, as the origins of its weak pointers would be unknown.
+    pub fn from_arc(mut arc: Arc<T>) -> Result<Self, Arc<T>> {
+        if Arc::get_mut(&mut arc).is_some() {
+            Ok(Self {
+                arc,
+                origins: Vec::new(),
+            })
+        } else {
+            Err(arc)
+        }
+    }
+
+    /// Unwraps the `Arc`, dropping the records.
+    pub fn into_arc(this: Self) -> Arc<T> {
+        this.arc
+    }
+
+    /// Creates a weak handle, recording the caller's location.
+    #[track_caller]
+    pub fn downgrade(this: &mut Self) -> RegisteredWeak<T> {
+        this.origins.retain(|origin| origin.strong_count() > 0);
+        let origin = Arc::new(Location::caller());
+        this.origins.push(Arc::downgrade(&origin));
+        RegisteredWeak {
+            weak: Arc::downgrade(&this.arc),
+            origin,
+        }
+    }
+
+    /// The number of weak pointers to the current allocation.
+    pub fn live_weaks(this: &Self) -> usize {
+        Arc::weak_count(&this.arc)
+    }
+
+    /// Where the weak handles to the current allocation that are still alive
+    /// were created, in order.
+    pub fn origins(this: &Self) -> Vec<&'static Location<'static>> {
+        this.origins
+            .iter()
+            .filter_map(|origin| origin.upgrade().map(|origin| *origin))
+            .collect()
+    }
+
+    /// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), and clears the
+    /// records, as no weak pointer can reach the value anymore.
+    ///
+    /// Returns None if the strong count was greater than 1 or a concurrent
+    /// weak upgrade won the race.
+    pub fn get_mut_drop_weak(&mut self) -> Option<&mut T> {
+        match get_mut_drop_weak_access(&mut self.arc) {
+            Access::Unchanged(value) | Access::Replaced(value) => {
+                self.origins.clear();
+                Some(value)
+            }
+            Access::Shared(_) => None,
+        }
+    }
+}
+
+impl<T> RegisteredWeak<T> {
+    /// Attempts to upgrade to a strong pointer.
+    pub fn upgrade(&self) -> Option<Arc<T>> {
+        self.weak.upgrade()
+    }
+
+    /// Where this handle, or the one it was cloned from, was created.
+    pub fn origin(&self) -> &'static Location<'static> {
+        *self.origin
+    }
+}
+
+impl<T> Clone for RegisteredWeak<T> {
+    fn clone(&self) -> Self {
+        Self {
+            weak: Weak::clone(&self.weak),
+            origin: Arc::clone(&self.origin),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for WeakRegistry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakRegistry")
+            .field("value", &**self)
+            .field("origins", &WeakRegistry::origins(self))
+            .finish()
+    }
+}
+
+impl<T> fmt::Debug for RegisteredWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegisteredWeak")
+            .field("origin", &self.origin())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Deref for WeakRegistry<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.arc
+    }
+}
@@ -0,0 +1,111 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+use event_listener::Event;
+#[cfg(feature = "std")]
+use event_listener::Listener;
+
+use crate::{SharedPointer, get_mut_drop_weak_detailed};
+
+/// Wakes threads and tasks waiting for exclusive access when other strong
+/// pointers are released.
+///
+/// [`get_mut_drop_weak_blocking`](crate::get_mut_drop_weak_blocking) and
+/// [`get_mut_drop_weak_async`](crate::get_mut_drop_weak_async) have to poll,
+/// because strong pointers don't announce when they are dropped. If the
+/// readers drop their pointers through [`ReleaseEvent::release`] instead, the
+/// waiting methods here sleep until then. Notifications go through the
+/// [`event-listener`](event_listener) crate, so the same event works with
+/// any async runtime and with plain threads.
+///
+/// Requires the `event-listener` feature.
+pub struct ReleaseEvent {
+    event: Event,
+}
+
+impl ReleaseEvent {
+    /// Creates an event with no waiters.
+    pub const fn new() -> Self {
+        ReleaseEvent {
+            event: Event::new(),
+        }
+    }
+
+    /// Drops `ptr` and wakes all waiters, so they can check whether they now
+    /// have exclusive access.
+    pub fn release<P>(&self, ptr: P) {
+        drop(ptr);
+        self.notify();
+    }
+
+    /// Wakes all waiters, e.g. after a strong pointer was dropped some other
+    /// way.
+    pub fn notify(&self) {
+        self.event.notify(usize::MAX);
+    }
+
+    /// Like [`get_mut_drop_weak_blocking`](crate::get_mut_drop_weak_blocking),
+    /// but sleeps until this event is notified instead of polling.
+    ///
+    /// Returns Err(&mut Arc<T>) if the pointer is still shared when the
+    /// timeout expires.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "call-sites", track_caller)]
+    pub fn get_mut_drop_weak_blocking<'a, T, P: SharedPointer<T>>(
+        &self,
+        ptr: &'a mut P,
+        timeout: Duration,
+    ) -> Result<&'a mut T, &'a mut P> {
+        let deadline = Instant::now().checked_add(timeout);
+        let mut ptr = ptr;
+        loop {
+            // Listen before checking, so that a release in between isn't missed.
+            let listener = self.event.listen();
+            match get_mut_drop_weak_detailed(ptr) {
+                Ok(value) => return Ok(value),
+                Err(err) if err.is_transient() => ptr = err.into_inner(),
+                Err(err) => return Err(err.into_inner()),
+            }
+            match deadline {
+                Some(deadline) => {
+                    if listener.wait_deadline(deadline).is_none() {
+                        return Err(ptr);
+                    }
+                }
+                None => listener.wait(),
+            }
+        }
+    }
+
+    /// Like [`get_mut_drop_weak_async`](crate::get_mut_drop_weak_async), but
+    /// the future sleeps until this event is notified instead of polling.
+    pub async fn get_mut_drop_weak_async<'a, T: 'a, P: SharedPointer<T>>(
+        &'a self,
+        ptr: &'a mut P,
+    ) -> Result<&'a mut T, &'a mut P> {
+        let mut ptr = ptr;
+        loop {
+            // Listen before checking, so that a release in between isn't missed.
+            let listener = self.event.listen();
+            match get_mut_drop_weak_detailed(ptr) {
+                Ok(value) => return Ok(value),
+                Err(err) if err.is_transient() => ptr = err.into_inner(),
+                Err(err) => return Err(err.into_inner()),
+            }
+            listener.await;
+        }
+    }
+}
+
+impl Default for ReleaseEvent {
+    fn default() -> Self {
+        ReleaseEvent::new()
+    }
+}
+
+impl fmt::Debug for ReleaseEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReleaseEvent").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,112 @@
+use alloc::sync::Arc;
+
+use crate::{Sealed, guard::moved_out, instrument, pointer::arc_get_mut_unchecked};
+
+/// Unsized payloads, typically trait objects, that know how to move
+/// themselves into a new allocation.
+///
+/// Implement this for `dyn YourTrait` by forwarding to a method of your trait
+/// with an `Arc<Self>` receiver, which in turn calls [`relocate`] on the
+/// concrete type:
+///
+/// ```
+/// use std::sync::Arc;
+/// use get_mut_drop_weak::{Relocate, get_mut_drop_weak_dyn, relocate};
+///
+/// trait Shape {
+///     fn grow(&mut self);
+///     fn relocate_arc(self: Arc<Self>) -> Result<Arc<dyn Shape>, Arc<dyn Shape>>;
+/// }
+///
+/// struct Square(u32);
+///
+/// impl Shape for Square {
+///     fn grow(&mut self) {
+///         self.0 += 1;
+///     }
+///     fn relocate_arc(self: Arc<Self>) -> Result<Arc<dyn Shape>, Arc<dyn Shape>> {
+///         relocate(self).map(|a| a as _).map_err(|a| a as _)
+///     }
+/// }
+///
+/// impl Relocate for dyn Shape {
+///     fn relocate(this: Arc<Self>) -> Result<Arc<Self>, Arc<Self>> {
+///         this.relocate_arc()
+///     }
+/// }
+///
+/// let mut shape: Arc<dyn Shape> = Arc::new(Square(1));
+/// let weak = Arc::downgrade(&shape);
+/// if let Ok(shape) = get_mut_drop_weak_dyn(&mut shape) {
+///     shape.grow();
+/// }
+/// assert!(weak.upgrade().is_none());
+/// ```
+pub trait Relocate {
+    /// Moves the value out of `this` into a new, unshared allocation, or
+    /// returns `this` unchanged if it can't.
+    ///
+    /// `this` is the only strong pointer when this is called, but weak
+    /// pointers may be upgraded concurrently.
+    fn relocate(this: Arc<Self>) -> Result<Arc<Self>, Arc<Self>>;
+}
+
+/// Moves the value out of `arc` into a new allocation if `arc` is the only
+/// strong pointer, orphaning any weak pointers. Otherwise returns `arc`.
+///
+/// This is the building block for [`Relocate`] implementations.
+pub fn relocate<T>(arc: Arc<T>) -> Result<Arc<T>, Arc<T>> {
+    if Arc::strong_count(&arc) > 1 {
+        return Err(arc);
+    }
+    let preallocated = Arc::<T>::new_uninit();
+    let mut arc = arc;
+    // SAFETY: `preallocated` came straight from `new_uninit` and was never shared.
+    if unsafe {
+        crate::replace(&mut arc, preallocated, Arc::try_unwrap, |u, value| {
+            Sealed::init(u, value)
+        })
+    } {
+        Ok(arc)
+    } else {
+        Err(arc)
+    }
+}
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but for unsized
+/// payloads such as trait objects, using their [`Relocate`] implementation to
+/// move the value into a new allocation.
+///
+/// If `Relocate::relocate` panics, the process is aborted.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_dyn<U: ?Sized + Relocate>(
+    arc: &mut Arc<U>,
+) -> Result<&mut U, &mut Arc<U>> {
+    if Arc::get_mut(arc).is_some() {
+        instrument::fast_path();
+        // SAFETY: `arc` was just checked to be exclusive.
+        return Ok(unsafe { arc_get_mut_unchecked(arc) });
+    }
+    let strong = Arc::strong_count(arc);
+    if strong > 1 {
+        instrument::strongly_shared::<U>(strong, Arc::weak_count(arc));
+        return Err(arc);
+    }
+
+    // Strong = 1, Weak > 0. Hand the pointer to the relocator.
+    let orphaned = Arc::weak_count(arc);
+    moved_out(arc, |original| {
+        let (Ok(relocated) | Err(relocated)) = U::relocate(original);
+        (relocated, ())
+    });
+    // The relocator is trusted to return either the original or a fresh
+    // pointer, but verify exclusivity rather than relying on it.
+    if Arc::get_mut(arc).is_some() {
+        instrument::replaced::<U>(orphaned);
+        // SAFETY: `arc` was just checked to be exclusive.
+        Ok(unsafe { arc_get_mut_unchecked(arc) })
+    } else {
+        instrument::lost_race::<U>(Arc::strong_count(arc), Arc::weak_count(arc));
+        Err(arc)
+    }
+}
@@ -0,0 +1,106 @@
+use alloc::sync::{Arc, Weak};
+
+use crate::{guard::moved_out, instrument};
+
+/// Values that keep a weak pointer to their own allocation, e.g. one handed
+/// out by [`Arc::new_cyclic`].
+///
+/// Replacing the allocation orphans that weak pointer along with all others.
+/// [`with_exclusive_repair`] calls [`repair`](Self::repair) to point it at
+/// the new allocation instead.
+pub trait RepairSelfWeak: Sized {
+    /// Stores `new_self` in place of the orphaned weak pointer to `self`.
+    ///
+    /// This is called while the new allocation is being constructed, so
+    /// `new_self` can't be upgraded yet.
+    fn repair(&mut self, new_self: &Weak<Self>);
+}
+
+/// Calls `f` with exclusive access to the value behind `arc`, orphaning weak
+/// pointers as [`get_mut_drop_weak`](crate::get_mut_drop_weak) does, except
+/// the value's weak pointer to itself, which is repaired with
+/// [`RepairSelfWeak::repair`].
+///
+/// A value with a live weak pointer to itself is never exclusively owned, so
+/// this always moves it to a new allocation. See [`with_exclusive_fixup`],
+/// which this calls with `repair` as the fixup.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn with_exclusive_repair<T: RepairSelfWeak, R>(
+    arc: &mut Arc<T>,
+    f: impl FnOnce(&mut T) -> R,
+) -> Result<R, &mut Arc<T>> {
+    with_exclusive_fixup(arc, f, T::repair)
+}
+
+/// Calls `f` with exclusive access to the value behind `arc`, orphaning weak
+/// pointers as [`get_mut_drop_weak`](crate::get_mut_drop_weak) does, and
+/// then, if the value was moved to a new allocation, calls `fixup` with a
+/// weak pointer to it, e.g. to re-register the value wherever the orphaned
+/// weak pointers were kept.
+///
+/// Once a weak pointer to the new allocation is out, the value is no longer
+/// exclusively owned, which is why this takes closures instead of returning
+/// `&mut T`. `fixup` runs while the new allocation is being constructed, with
+/// [`Arc::new_cyclic`], so `new_self` can't be upgraded until it returns. If
+/// there were no weak pointers, `f` is called in place and `fixup` isn't
+/// called.
+///
+/// Returns Err(&mut Arc<T>), without calling either closure, if the strong
+/// count was greater than 1 or a concurrent weak upgrade won the race. If `f`
+/// or `fixup` panics during a replacement, the process is aborted.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn with_exclusive_fixup<T, R>(
+    arc: &mut Arc<T>,
+    f: impl FnOnce(&mut T) -> R,
+    fixup: impl FnOnce(&mut T, &Weak<T>),
+) -> Result<R, &mut Arc<T>> {
+    if let Some(value) = Arc::get_mut(arc) {
+        instrument::fast_path();
+        return Ok(f(value));
+    }
+    let mut result = None;
+    let replaced = update_cyclic(arc, |mut value, new_self| {
+        result = Some(f(&mut value));
+        fixup(&mut value, new_self);
+        value
+    });
+    if replaced {
+        Ok(result.expect("called on success"))
+    } else {
+        Err(arc)
+    }
+}
+
+/// Rebuilds the value behind `arc` into a new allocation with
+/// [`Arc::new_cyclic`], orphaning all weak pointers to the old one.
+///
+/// `f` gets the moved value and a weak pointer to the new allocation, and
+/// returns the new value, e.g. after storing the weak pointer in it or
+/// handing it to observers. As with `new_cyclic`, the weak pointer can't be
+/// upgraded until `f` returns.
+///
+/// A new allocation is made even if there were no weak pointers, as `f` needs
+/// a weak pointer to it.
+///
+/// Returns false, without calling `f`, if the strong count was greater than
+/// 1 or a concurrent weak upgrade won the race. If `f` panics, the process is
+/// aborted.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn update_cyclic<T>(arc: &mut Arc<T>, f: impl FnOnce(T, &Weak<T>) -> T) -> bool {
+    let strong = Arc::strong_count(arc);
+    if strong > 1 {
+        instrument::strongly_shared::<T>(strong, Arc::weak_count(arc));
+        return false;
+    }
+    let orphaned = Arc::weak_count(arc);
+    let replaced = moved_out(arc, |original| match Arc::try_unwrap(original) {
+        Ok(value) => (Arc::new_cyclic(|new_self| f(value, new_self)), true),
+        Err(restored) => (restored, false),
+    });
+    if replaced {
+        instrument::replaced::<T>(orphaned);
+    } else {
+        instrument::lost_race::<T>(Arc::strong_count(arc), Arc::weak_count(arc));
+    }
+    replaced
+}
@@ -0,0 +1,41 @@
+use core::hint;
+
+use crate::{FailureReason, GetMutError, ReplacementCache, SharedPointer, cache::cached_detailed};
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but retries up to
+/// `attempts` times in total if a concurrent weak upgrade wins the race.
+///
+/// A weak pointer upgraded during the replacement often lives only briefly,
+/// e.g. in a cache lookup. After losing a race, the pointer is therefore
+/// re-checked, and a strong count above 1 is treated as that upgrade still
+/// being alive rather than as a reason to give up. If the pointer is strongly
+/// shared on the first attempt, this returns immediately, as usual.
+///
+/// The allocation made for an attempt that lost the race is reused by the
+/// next one.
+///
+/// An `attempts` of 0 is treated as 1.
+///
+/// Returns Err(&mut Arc<T>) if the pointer is still shared after the last
+/// attempt.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_retry<T, P: SharedPointer<T>>(
+    ptr: &mut P,
+    attempts: usize,
+) -> Result<&mut T, &mut P> {
+    let mut ptr = ptr;
+    let mut raced = false;
+    let mut cache = ReplacementCache::new();
+    for _ in 1..attempts {
+        match cached_detailed(ptr, &mut cache) {
+            Ok(value) => return Ok(value),
+            Err(err) if raced || err.reason() == FailureReason::LostRace => {
+                raced = true;
+                ptr = err.into_inner();
+                hint::spin_loop();
+            }
+            Err(err) => return Err(err.into_inner()),
+        }
+    }
+    cached_detailed(ptr, &mut cache).map_err(GetMutError::into_inner)
+}
@@ -0,0 +1,116 @@
+//! Versions of the main functions written without any unsafe code, for
+//! consumers that only accept audited-safe code paths. The module is
+//! `#![forbid(unsafe_code)]`.
+//!
+//! Moving a value out of a weakly shared pointer without a placeholder needs
+//! unsafe code, so these functions ask more of `T` instead:
+//! [`get_mut_drop_weak`] swaps in a pointer to `T::default()` while it
+//! unwraps the original, and [`clone_replace_drop_weak`] and
+//! [`make_mut_drop_weak`] clone the value. The fast path uses the checked
+//! `get_mut`.
+//!
+//! Requires the `safe` feature, which only adds this module and changes
+//! nothing else in the crate.
+
+#![forbid(unsafe_code)]
+
+use core::mem;
+
+use crate::{FailureReason, GetMutError, SharedPointer, instrument};
+
+/// Like [`crate::get_mut_drop_weak`], but moves the value to the new
+/// allocation by swapping in a pointer to `T::default()` and unwrapping the
+/// original, instead of moving it out with unsafe code.
+///
+/// Returns Err(&mut Arc<T>) if the strong count was greater than 1 or a
+/// concurrent weak upgrade won the race.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak<T: Default, P: SharedPointer<T>>(ptr: &mut P) -> Result<&mut T, &mut P> {
+    get_mut_drop_weak_detailed(ptr).map_err(GetMutError::into_inner)
+}
+
+/// Like [`crate::get_mut_drop_weak_detailed`], built as
+/// [`get_mut_drop_weak`] is.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_detailed<T: Default, P: SharedPointer<T>>(
+    ptr: &mut P,
+) -> Result<&mut T, GetMutError<'_, P>> {
+    if P::is_unique(ptr) {
+        instrument::fast_path();
+        return Ok(P::get_mut(ptr).expect("checked above"));
+    }
+    let strong = P::strong_count(ptr);
+    let weak = P::weak_count(ptr);
+    if strong > 1 {
+        instrument::strongly_shared::<T>(strong, weak);
+        return Err(GetMutError::new(
+            ptr,
+            strong,
+            weak,
+            FailureReason::StronglyShared,
+        ));
+    }
+
+    // State: Strong = 1, Weak > 0. Unwrap the original behind a placeholder.
+    let placeholder = P::new_like(ptr, T::default());
+    let original = mem::replace(ptr, placeholder);
+    match P::try_unwrap(original) {
+        Ok(value) => {
+            instrument::replaced::<T>(weak);
+            let slot = P::get_mut(ptr).expect("fresh allocation is unique");
+            *slot = value;
+            Ok(slot)
+        }
+        Err(original) => {
+            // Lost the race to a weak upgrade; put the original back.
+            *ptr = original;
+            let (strong, weak) = (P::strong_count(ptr), P::weak_count(ptr));
+            instrument::lost_race::<T>(strong, weak);
+            Err(GetMutError::new(ptr, strong, weak, FailureReason::LostRace))
+        }
+    }
+}
+
+/// Like [`crate::clone_replace_drop_weak`]: orphans weak pointers by cloning
+/// the value into a new allocation.
+///
+/// Returns Err(&mut Arc<T>) if the strong count was greater than 1.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn clone_replace_drop_weak<T: Clone, P: SharedPointer<T>>(
+    ptr: &mut P,
+) -> Result<&mut T, &mut P> {
+    if P::is_unique(ptr) {
+        instrument::fast_path();
+        return Ok(P::get_mut(ptr).expect("checked above"));
+    }
+    let (strong, weak) = (P::strong_count(ptr), P::weak_count(ptr));
+    if strong > 1 {
+        instrument::strongly_shared::<T>(strong, weak);
+        return Err(ptr);
+    }
+    Ok(clone_into_new(ptr))
+}
+
+/// Like [`crate::make_mut_drop_weak`]: clones the value into a new
+/// allocation unless the pointer is already exclusive.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn make_mut_drop_weak<T: Clone, P: SharedPointer<T>>(ptr: &mut P) -> &mut T {
+    if P::is_unique(ptr) {
+        instrument::fast_path();
+        return P::get_mut(ptr).expect("checked above");
+    }
+    clone_into_new(ptr)
+}
+
+/// Points `ptr` at a new allocation holding a clone of its value.
+#[cfg_attr(feature = "call-sites", track_caller)]
+fn clone_into_new<T: Clone, P: SharedPointer<T>>(ptr: &mut P) -> &mut T {
+    instrument::cloned();
+    // Only the weak pointers lose the value if this was the last strong one.
+    let orphaning = P::strong_count(ptr) == 1;
+    *ptr = P::new_like(ptr, T::clone(ptr));
+    if orphaning {
+        instrument::orphaned();
+    }
+    P::get_mut(ptr).expect("fresh allocation is unique")
+}
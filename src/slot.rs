@@ -0,0 +1,26 @@
+use alloc::sync::Arc;
+
+use crate::{get_mut_drop_weak, instrument};
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak) for an optional
+/// pointer, first filling an empty `slot` with a new `Arc` holding the result
+/// of `f`.
+///
+/// Returns Err(&mut Arc<T>), without calling `f`, if `slot` held a pointer
+/// whose strong count was greater than 1 or a concurrent weak upgrade won the
+/// race.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_or_insert_with<T>(
+    slot: &mut Option<Arc<T>>,
+    f: impl FnOnce() -> T,
+) -> Result<&mut T, &mut Arc<T>> {
+    match slot {
+        Some(arc) => get_mut_drop_weak(arc),
+        None => {
+            let arc = slot.insert(Arc::new(f()));
+            instrument::fast_path();
+            // SAFETY: `arc` was just allocated and hasn't been shared.
+            Ok(unsafe { crate::pointer::arc_get_mut_unchecked(arc) })
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use core::{
+    hint,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// A minimal spin lock for critical sections that only touch a few counters.
+pub(crate) struct SpinLock(AtomicBool);
+
+pub(crate) struct SpinGuard<'a>(&'a SpinLock);
+
+impl SpinLock {
+    pub(crate) const fn new() -> Self {
+        Self(AtomicBool::new(false))
+    }
+
+    pub(crate) fn lock(&self) -> SpinGuard<'_> {
+        while self
+            .0
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+        SpinGuard(self)
+    }
+
+    pub(crate) fn try_lock(&self) -> Option<SpinGuard<'_>> {
+        self.0
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+            .then_some(SpinGuard(self))
+    }
+}
+
+impl Drop for SpinGuard<'_> {
+    fn drop(&mut self) {
+        self.0.0.store(false, Ordering::Release);
+    }
+}
@@ -0,0 +1,20 @@
+use crate::{FailureReason, GetMutError, SharedPointer, unique_or_else};
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak), but never moves the
+/// value to a new allocation, for callers relying on
+/// [`Arc::as_ptr`](alloc::sync::Arc::as_ptr) staying the same.
+///
+/// This is `Arc::get_mut` with the reason for failure, where weak pointers
+/// blocking exclusive access are reported as
+/// [`FailureReason::WouldReplace`], distinct from strong sharing, so the
+/// caller can decide whether to fall back to a replacing variant.
+pub fn get_mut_stable<T, P: SharedPointer<T>>(ptr: &mut P) -> Result<&mut T, GetMutError<'_, P>> {
+    unique_or_else(ptr, |ptr| {
+        let (strong, weak) = (P::strong_count(ptr), P::weak_count(ptr));
+        let reason = match strong {
+            1 => FailureReason::WouldReplace,
+            _ => FailureReason::StronglyShared,
+        };
+        Err(GetMutError::new(ptr, strong, weak, reason))
+    })
+}
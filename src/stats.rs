@@ -0,0 +1,61 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+pub(crate) static FAST_PATH: AtomicUsize = AtomicUsize::new(0);
+pub(crate) static REPLACEMENTS: AtomicUsize = AtomicUsize::new(0);
+pub(crate) static CLONES: AtomicUsize = AtomicUsize::new(0);
+pub(crate) static FAILURES: AtomicUsize = AtomicUsize::new(0);
+
+/// Process-wide counts of what the crate's functions did, as returned by
+/// [`stats`].
+///
+/// Requires the `stats` feature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Stats {
+    /// Pointers that were already exclusive.
+    pub fast_path: usize,
+    /// Values moved to a new allocation, orphaning weak pointers.
+    pub replacements: usize,
+    /// Values cloned into a new allocation, because the pointer was strongly
+    /// shared or to orphan its weak pointers without moving the value.
+    pub clones: usize,
+    /// Attempts refused because the pointer was strongly shared, or because
+    /// a concurrent weak upgrade won the race.
+    pub failures: usize,
+}
+
+/// The counts recorded since the process started, or since the last
+/// [`reset_stats`].
+///
+/// The counters are updated independently, so a snapshot taken while other
+/// threads are using the crate may be slightly inconsistent.
+///
+/// ```
+/// use std::sync::Arc;
+/// use get_mut_drop_weak::{make_mut_drop_weak, stats};
+///
+/// let before = stats();
+/// let mut arc = Arc::new(1);
+/// let _other = Arc::clone(&arc);
+/// *make_mut_drop_weak(&mut arc) += 1;
+/// assert!(stats().clones > before.clones);
+/// ```
+///
+/// Requires the `stats` feature.
+pub fn stats() -> Stats {
+    Stats {
+        fast_path: FAST_PATH.load(Ordering::Relaxed),
+        replacements: REPLACEMENTS.load(Ordering::Relaxed),
+        clones: CLONES.load(Ordering::Relaxed),
+        failures: FAILURES.load(Ordering::Relaxed),
+    }
+}
+
+/// Sets all counters back to zero, e.g. at the start of a test.
+///
+/// Requires the `stats` feature.
+pub fn reset_stats() {
+    for counter in [&FAST_PATH, &REPLACEMENTS, &CLONES, &FAILURES] {
+        counter.store(0, Ordering::Relaxed);
+    }
+}
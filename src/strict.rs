@@ -0,0 +1,126 @@
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+
+use crate::SharedPointer;
+
+/// Marks types that never keep a weak pointer to their own allocation.
+///
+/// [`get_mut_drop_weak`](crate::get_mut_drop_weak) orphans every weak
+/// pointer, including one a value keeps to itself (e.g. from
+/// [`Arc::new_cyclic`](alloc::sync::Arc::new_cyclic)), which silently breaks
+/// that link. [`get_mut_drop_weak_strict`] only accepts types implementing
+/// this trait, so that the promise is stated once, next to the type, and
+/// checked at every call site. Types that do keep such a pointer can use
+/// [`with_exclusive_repair`](crate::with_exclusive_repair) instead.
+///
+/// Nothing checks the promise itself, but breaking it is only a logic error.
+/// Implemented for primitive types, `str` and `String`, and for `Box`, `Vec`,
+/// `Option`, tuples, arrays, slices, `BTreeMap` and (with the `std` feature)
+/// `HashMap` of types implementing it.
+///
+/// ```compile_fail
+/// use std::sync::{Arc, Weak};
+/// use get_mut_drop_weak::get_mut_drop_weak_strict;
+///
+/// struct Node {
+///     this: Weak<Node>,
+/// }
+///
+/// let mut arc = Arc::new_cyclic(|this| Node { this: this.clone() });
+/// let _ = get_mut_drop_weak_strict(&mut arc);
+/// ```
+pub trait NoSelfWeak {}
+
+macro_rules! impl_no_self_weak {
+    ($($ty:ty),*) => {
+        $(impl NoSelfWeak for $ty {})*
+    };
+}
+
+impl_no_self_weak!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    str,
+    String
+);
+
+impl<T: NoSelfWeak + ?Sized> NoSelfWeak for Box<T> {}
+impl<T: NoSelfWeak> NoSelfWeak for Vec<T> {}
+impl<T: NoSelfWeak> NoSelfWeak for Option<T> {}
+impl<T: NoSelfWeak> NoSelfWeak for [T] {}
+impl<T: NoSelfWeak, const N: usize> NoSelfWeak for [T; N] {}
+impl<K: NoSelfWeak, V: NoSelfWeak> NoSelfWeak for BTreeMap<K, V> {}
+#[cfg(feature = "std")]
+impl<K: NoSelfWeak, V: NoSelfWeak, S> NoSelfWeak for std::collections::HashMap<K, V, S> {}
+
+macro_rules! impl_no_self_weak_tuple {
+    ($($name:ident)+) => {
+        impl<$($name: NoSelfWeak),+> NoSelfWeak for ($($name,)+) {}
+    };
+}
+
+impl_no_self_weak_tuple!(A);
+impl_no_self_weak_tuple!(A B);
+impl_no_self_weak_tuple!(A B C);
+impl_no_self_weak_tuple!(A B C D);
+impl_no_self_weak_tuple!(A B C D E);
+impl_no_self_weak_tuple!(A B C D E F);
+impl_no_self_weak_tuple!(A B C D E F G);
+impl_no_self_weak_tuple!(A B C D E F G H);
+impl_no_self_weak_tuple!(A B C D E F G H I);
+impl_no_self_weak_tuple!(A B C D E F G H I J);
+impl_no_self_weak_tuple!(A B C D E F G H I J K);
+impl_no_self_weak_tuple!(A B C D E F G H I J K L);
+
+/// [`get_mut_drop_weak`](crate::get_mut_drop_weak), restricted to types
+/// that promise not to keep a weak pointer to themselves.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_strict<T: NoSelfWeak, P: SharedPointer<T>>(
+    ptr: &mut P,
+) -> Result<&mut T, &mut P> {
+    crate::get_mut_drop_weak(ptr)
+}
+
+/// Permission to orphan weak pointers, required by
+/// [`get_mut_drop_weak_authorized`].
+///
+/// The token is zero-sized and can only be created with
+/// [`OrphanWeaks::allow_orphaning_weak_pointers`], so every place that is
+/// allowed to invalidate weak pointers can be found by searching for that
+/// name, and stands out in review. It can be passed down to the code that
+/// needs it like any other argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OrphanWeaks(());
+
+impl OrphanWeaks {
+    /// Creates the token, stating that nothing depends on the weak pointers
+    /// of the values it will be used on staying upgradable.
+    pub const fn allow_orphaning_weak_pointers() -> Self {
+        Self(())
+    }
+}
+
+/// [`get_mut_drop_weak`](crate::get_mut_drop_weak), for call sites that
+/// were explicitly allowed to orphan weak pointers with an [`OrphanWeaks`]
+/// token.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_authorized<T, P: SharedPointer<T>>(
+    ptr: &mut P,
+    _token: OrphanWeaks,
+) -> Result<&mut T, &mut P> {
+    crate::get_mut_drop_weak(ptr)
+}
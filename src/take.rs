@@ -0,0 +1,67 @@
+use core::mem;
+
+use crate::{SharedPointer, instrument};
+
+/// The [`mem::take`] analog for shared pointers: moves the value out and
+/// leaves a new pointer to `T::default()` in its place, orphaning any weak
+/// pointers.
+///
+/// If there are no weak pointers, the value is taken in place without
+/// allocating.
+///
+/// Returns None, leaving the pointer untouched, if the strong count was
+/// greater than 1 or a concurrent weak upgrade won the race.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn take_drop_weak<T: Default, P: SharedPointer<T>>(ptr: &mut P) -> Option<T> {
+    if let Some(value) = P::get_mut(ptr) {
+        instrument::fast_path();
+        return Some(mem::take(value));
+    }
+    let strong = P::strong_count(ptr);
+    if strong > 1 {
+        instrument::strongly_shared::<T>(strong, P::weak_count(ptr));
+        return None;
+    }
+    replace_drop_weak(ptr, T::default()).ok()
+}
+
+/// The [`mem::replace`] analog for shared pointers: installs `new` in a new
+/// pointer, orphaning any weak pointers, and returns the old value.
+///
+/// If there are no weak pointers, the value is replaced in place without
+/// allocating.
+///
+/// Returns Err(new), leaving the pointer untouched, if the strong count was
+/// greater than 1 or a concurrent weak upgrade won the race.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn replace_drop_weak<T, P: SharedPointer<T>>(ptr: &mut P, new: T) -> Result<T, T> {
+    if let Some(value) = P::get_mut(ptr) {
+        instrument::fast_path();
+        return Ok(mem::replace(value, new));
+    }
+    let strong = P::strong_count(ptr);
+    if strong > 1 {
+        instrument::strongly_shared::<T>(strong, P::weak_count(ptr));
+        return Err(new);
+    }
+
+    // State: Strong = 1, Weak > 0. Swap in a fresh pointer and unwrap the original.
+    let orphaned = P::weak_count(ptr);
+    let fresh = P::new_like(ptr, new);
+    let original = mem::replace(ptr, fresh);
+    match P::try_unwrap(original) {
+        Ok(value) => {
+            instrument::replaced::<T>(orphaned);
+            Ok(value)
+        }
+        Err(original) => {
+            // Lost the race to a weak upgrade; put the original back.
+            let fresh = mem::replace(ptr, original);
+            instrument::lost_race::<T>(P::strong_count(ptr), P::weak_count(ptr));
+            match P::try_unwrap(fresh) {
+                Ok(new) => Err(new),
+                Err(_) => unreachable!("fresh allocation is unique"),
+            }
+        }
+    }
+}
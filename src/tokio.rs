@@ -0,0 +1,25 @@
+//! An async [`lock_or_own`] for `tokio` mutexes, which skips the lock when
+//! the [`Arc`] holding it can be made exclusive.
+//!
+//! Requires the `tokio` feature.
+
+use alloc::sync::Arc;
+
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::{OwnOr, get_mut_drop_weak};
+
+/// The result of [`lock_or_own`]: exclusive access to the value in a
+/// `Mutex`, with or without the lock.
+pub type OwnOrLock<'a, T> = OwnOr<'a, T, MutexGuard<'a, T>>;
+
+/// Exclusive access to the value in `arc`'s `Mutex`, skipping the lock, and
+/// without ever yielding, when the `Arc` can be made exclusive with
+/// [`get_mut_drop_weak`](crate::get_mut_drop_weak), orphaning weak pointers
+/// as needed, and awaiting the lock otherwise.
+pub async fn lock_or_own<T>(arc: &mut Arc<Mutex<T>>) -> OwnOrLock<'_, T> {
+    match get_mut_drop_weak(arc) {
+        Ok(mutex) => OwnOrLock::Own(mutex.get_mut()),
+        Err(arc) => OwnOrLock::Locked(arc.lock().await),
+    }
+}
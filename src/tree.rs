@@ -0,0 +1,62 @@
+//! Exclusive access to nodes whose children point back at them weakly.
+//!
+//! In the usual parent-pointer tree, every child holds a `Weak` to its
+//! parent, so a parent is never exclusively owned and
+//! [`get_mut_drop_weak`](crate::get_mut_drop_weak) would orphan all of those
+//! links. [`with_exclusive_reparent`] replaces the parent like
+//! `get_mut_drop_weak` and then points the children at the new allocation.
+
+use alloc::sync::{Arc, Weak};
+
+use crate::with_exclusive_fixup;
+
+/// A node whose children hold weak pointers to it.
+///
+/// The children are typically `Arc`s themselves, shared with other code, so
+/// their parent link is re-pointed through a shared reference and needs
+/// interior mutability, e.g. a `Mutex<Weak<Parent>>`.
+pub trait Children: Sized {
+    /// The type of the children, e.g. `Arc<Node>`.
+    type Child;
+
+    /// The children of this node.
+    fn children(&self) -> impl Iterator<Item = &Self::Child>;
+
+    /// Points `child`'s parent link at `parent`.
+    fn set_parent(child: &Self::Child, parent: &Weak<Self>);
+}
+
+/// Calls `f` with exclusive access to the node behind `arc`, orphaning weak
+/// pointers as [`get_mut_drop_weak`](crate::get_mut_drop_weak) does, and
+/// then points the parent links of its children at the node's new
+/// allocation.
+///
+/// This is [`with_exclusive_fixup`] with the children walk as the fixup: the
+/// children are visited after `f`, so children it added are re-pointed too,
+/// and they can't upgrade their new parent link until the walk is done.
+/// Unlike a fixup, the walk also runs if the node had no weak pointers and
+/// stayed in place, to link up the children `f` added. Only the direct
+/// children are visited; grandchildren point at their parents, which stay
+/// where they are.
+///
+/// Returns Err(&mut Arc<T>), without calling `f`, if the strong count was
+/// greater than 1 (which includes a child that upgraded its parent link and
+/// still holds the result) or a concurrent weak upgrade won the race. If `f`
+/// or `set_parent` panics during a replacement, the process is aborted.
+pub fn with_exclusive_reparent<T: Children, R>(
+    arc: &mut Arc<T>,
+    f: impl FnOnce(&mut T) -> R,
+) -> Result<R, &mut Arc<T>> {
+    if let Some(node) = Arc::get_mut(arc) {
+        let result = f(node);
+        reparent(&**arc, &Arc::downgrade(arc));
+        return Ok(result);
+    }
+    with_exclusive_fixup(arc, f, |node, new_self| reparent(node, new_self))
+}
+
+fn reparent<T: Children>(node: &T, parent: &Weak<T>) {
+    for child in node.children() {
+        T::set_parent(child, parent);
+    }
+}
@@ -0,0 +1,65 @@
+use alloc::sync::Arc;
+use core::{mem::MaybeUninit, ptr};
+
+use crate::{SharedPointer, instrument, pointer::arc_get_mut_unchecked};
+
+/// [`get_mut_drop_weak`](crate::get_mut_drop_weak) for possibly-uninitialized
+/// payloads such as `Arc<MaybeUninit<T>>`.
+///
+/// The replacement moves the `MaybeUninit<T>` as plain bytes, so the payload
+/// is never assumed to be initialized. This is the same as calling
+/// `get_mut_drop_weak` directly and exists to make that guarantee explicit.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn get_mut_drop_weak_uninit<T, P: SharedPointer<MaybeUninit<T>>>(
+    ptr: &mut P,
+) -> Result<&mut MaybeUninit<T>, &mut P> {
+    crate::get_mut_drop_weak(ptr)
+}
+
+/// Like [`get_mut_drop_weak_uninit`], but for uninitialized slices, e.g. a
+/// shared buffer that is filled incrementally.
+///
+/// A slice can't be moved out of its `Arc`, so when only weak pointers block
+/// exclusive access, the elements are copied bitwise into a new allocation
+/// and the original is dropped (which drops nothing, as the elements are
+/// `MaybeUninit`). If another thread upgrades a weak pointer in the meantime,
+/// it keeps the original allocation, whose elements are then bitwise
+/// duplicates of the new ones.
+///
+/// Returns Err(&mut Arc<[MaybeUninit<T>]>) if the strong count was greater
+/// than 1.
+///
+/// # Safety
+/// Unless `T: Copy`, a duplicated element must only be dropped or read out
+/// once. If a weak pointer to `arc` may be upgraded concurrently, the caller
+/// must ensure that whoever upgrades it treats the initialized elements of
+/// the original allocation as moved out, e.g. by never reading them with
+/// `assume_init_read` or dropping them in place.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub unsafe fn get_mut_drop_weak_uninit_slice<T>(
+    arc: &mut Arc<[MaybeUninit<T>]>,
+) -> Result<&mut [MaybeUninit<T>], &mut Arc<[MaybeUninit<T>]>> {
+    if Arc::get_mut(arc).is_some() {
+        instrument::fast_path();
+    } else {
+        let strong = Arc::strong_count(arc);
+        if strong > 1 {
+            instrument::strongly_shared::<[MaybeUninit<T>]>(strong, Arc::weak_count(arc));
+            return Err(arc);
+        }
+        // Strong = 1, Weak > 0. Replace with an unshared copy.
+        let mut fresh = Arc::<[T]>::new_uninit_slice(arc.len());
+        // SAFETY: `fresh` was just allocated and has the same length as `arc`.
+        // Copying `MaybeUninit` bytes never reads them as initialized.
+        unsafe {
+            let dst = Arc::get_mut(&mut fresh).unwrap_unchecked();
+            ptr::copy_nonoverlapping(arc.as_ptr(), dst.as_mut_ptr(), dst.len());
+        }
+        *arc = fresh;
+        instrument::cloned();
+        instrument::orphaned();
+    }
+    // SAFETY: Either `Arc::get_mut` succeeded, or `arc` was just replaced by a
+    // fresh allocation that nothing else can reach.
+    Ok(unsafe { arc_get_mut_unchecked(arc) })
+}
@@ -0,0 +1,76 @@
+use alloc::sync::Arc;
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use crate::Sealed;
+use crate::get_mut_drop_weak;
+
+/// An [`Arc`] that is statically known to be the only pointer to its value:
+/// strong count 1, weak count 0.
+///
+/// Since exclusivity was proven once, [`DerefMut`] is infallible and doesn't
+/// repeat the count checks.
+///
+/// This lets a value be built exclusively, shared with [`UniqueArc::into_arc`],
+/// and later reclaimed with [`UniqueArc::from_arc`].
+pub struct UniqueArc<T>(Arc<T>);
+
+impl<T> UniqueArc<T> {
+    /// Allocates `value` in a new, unshared `Arc`.
+    pub fn new(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// Reclaims exclusive ownership of `arc`. Same as [`into_unique`].
+    pub fn from_arc(arc: Arc<T>) -> Result<Self, Arc<T>> {
+        into_unique(arc)
+    }
+
+    /// Converts back into a plain `Arc`, e.g. to start sharing the value.
+    pub fn into_arc(this: Self) -> Arc<T> {
+        this.0
+    }
+}
+
+impl<T> From<UniqueArc<T>> for Arc<T> {
+    fn from(unique: UniqueArc<T>) -> Self {
+        UniqueArc::into_arc(unique)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for UniqueArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> Deref for UniqueArc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for UniqueArc<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: The inner `Arc` is never shared and never downgraded.
+        unsafe { Sealed::get_mut_unchecked(&mut self.0) }
+    }
+}
+
+/// Proves that `arc` is the only strong pointer, orphaning weak pointers as
+/// [`get_mut_drop_weak`] does, and returns it as a [`UniqueArc`].
+///
+/// Returns Err(arc) if the strong count was greater than 1 or a concurrent
+/// weak upgrade won the race.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn into_unique<T>(arc: Arc<T>) -> Result<UniqueArc<T>, Arc<T>> {
+    let mut arc = arc;
+    match get_mut_drop_weak(&mut arc) {
+        Ok(_) => Ok(UniqueArc(arc)),
+        Err(_) => Err(arc),
+    }
+}
@@ -0,0 +1,75 @@
+use core::{error, fmt};
+
+use crate::SharedPointer;
+use crate::{get_mut_drop_weak, guard::moved_out};
+
+/// Why [`try_update`] didn't apply the update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateError<E> {
+    /// The strong count was greater than 1 or a concurrent weak upgrade won
+    /// the race, so the closure wasn't called.
+    Shared,
+    /// The closure failed with this error.
+    Failed(E),
+}
+
+impl<E: fmt::Display> fmt::Display for UpdateError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpdateError::Shared => f.write_str("value is shared"),
+            UpdateError::Failed(e) => write!(f, "update failed: {e}"),
+        }
+    }
+}
+
+impl<E: error::Error + 'static> error::Error for UpdateError<E> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            UpdateError::Shared => None,
+            UpdateError::Failed(e) => Some(e),
+        }
+    }
+}
+
+/// Rebuilds the value behind `ptr` by value with a fallible `f`, orphaning
+/// weak pointers as [`get_mut_drop_weak`] does.
+///
+/// Like [`update_with`], but `f` may fail, handing the value back together
+/// with its error. The value it hands back, whether on success or failure, is
+/// written back into the allocation, and the error is returned. Weak
+/// pointers are orphaned before `f` runs either way.
+///
+/// Returns Err(UpdateError::Shared), without calling `f`, if the strong count
+/// was greater than 1 or a concurrent weak upgrade won the race. If `f`
+/// panics, the process is aborted.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn try_update<T, E, P: SharedPointer<T>>(
+    ptr: &mut P,
+    f: impl FnOnce(T) -> Result<T, (T, E)>,
+) -> Result<(), UpdateError<E>> {
+    let Ok(slot) = get_mut_drop_weak(ptr) else {
+        return Err(UpdateError::Shared);
+    };
+    moved_out(slot, |value| match f(value) {
+        Ok(value) => (value, Ok(())),
+        Err((value, e)) => (value, Err(UpdateError::Failed(e))),
+    })
+}
+
+/// Rebuilds the value behind `ptr` by value, orphaning weak pointers as
+/// [`get_mut_drop_weak`] does.
+///
+/// Useful for types that are easier to consume and rebuild than to mutate
+/// through `&mut`, such as enums that change variant.
+///
+/// Returns false, without calling `f`, if the strong count was greater than
+/// 1 or a concurrent weak upgrade won the race. If `f` panics, the process is
+/// aborted.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn update_with<T, P: SharedPointer<T>>(ptr: &mut P, f: impl FnOnce(T) -> T) -> bool {
+    let Ok(slot) = get_mut_drop_weak(ptr) else {
+        return false;
+    };
+    moved_out(slot, |value| (f(value), ()));
+    true
+}
@@ -0,0 +1,30 @@
+use alloc::sync::{Arc, Weak};
+use core::hash::{BuildHasher, Hash};
+use std::collections::HashMap;
+
+use crate::{get_mut_drop_weak, pointer::arc_get_mut_unchecked};
+
+/// Like [`get_mut_drop_weak`](crate::get_mut_drop_weak) for a value cached in
+/// `map` under `key`, and points the entry at `arc` again, so the cache keeps
+/// working after the replacement.
+///
+/// On success, `map[key]` is a fresh weak pointer to `arc`, whatever it held
+/// before. It can't be upgraded while the returned reference is alive, as
+/// that also borrows `map`.
+///
+/// Returns Err(&mut Arc<V>), leaving `map` untouched, if the strong count was
+/// greater than 1 or a concurrent weak upgrade won the race.
+#[cfg_attr(feature = "call-sites", track_caller)]
+pub fn refresh_cache_entry<'a, K: Eq + Hash, V, S: BuildHasher>(
+    map: &'a mut HashMap<K, Weak<V>, S>,
+    key: K,
+    arc: &'a mut Arc<V>,
+) -> Result<&'a mut V, &'a mut Arc<V>> {
+    if get_mut_drop_weak(&mut *arc).is_err() {
+        return Err(arc);
+    }
+    map.insert(key, Arc::downgrade(arc));
+    // SAFETY: `arc` is the only strong pointer, and its only weak pointer is
+    // in `map`, which stays mutably borrowed as long as the result.
+    Ok(unsafe { arc_get_mut_unchecked(arc) })
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use get_mut_drop_weak::{
+    Access, get_mut_drop_weak_access, get_mut_drop_weak_counted, get_mut_drop_weak_with_old,
+};
+
+#[test]
+fn test_access_unchanged() {
+    let mut arc = Arc::new(1);
+    assert!(matches!(
+        get_mut_drop_weak_access(&mut arc),
+        Access::Unchanged(_)
+    ));
+}
+
+#[test]
+fn test_access_replaced_invalidates_pointer_keyed_cache() {
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+    let mut cache = HashMap::new();
+    cache.insert(Arc::as_ptr(&arc), "derived");
+
+    let old_key = Arc::as_ptr(&arc);
+    match get_mut_drop_weak_access(&mut arc) {
+        Access::Replaced(value) => {
+            *value += 1;
+            cache.remove(&old_key);
+        }
+        other => panic!("expected Replaced, got {other:?}"),
+    }
+    assert!(cache.is_empty());
+    assert_eq!(*arc, 2);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_access_shared() {
+    let mut arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+
+    let access = get_mut_drop_weak_access(&mut arc);
+    assert!(!access.is_replaced());
+    assert!(Arc::ptr_eq(access.into_result().unwrap_err(), &other));
+}
+
+#[test]
+fn test_counted_reports_orphaned_weaks() {
+    let mut arc = Arc::new(1);
+    let (_, orphaned) = get_mut_drop_weak_counted(&mut arc).unwrap();
+    assert_eq!(orphaned, 0);
+
+    let weaks: Vec<_> = (0..3).map(|_| Arc::downgrade(&arc)).collect();
+    let (value, orphaned) = get_mut_drop_weak_counted(&mut arc).unwrap();
+    *value += 1;
+    assert_eq!(orphaned, 3);
+    assert!(weaks.iter().all(|weak| weak.upgrade().is_none()));
+}
+
+#[test]
+fn test_with_old_migrates_side_table() {
+    let mut arc = Arc::new(1);
+    let mut side_table = HashMap::new();
+    side_table.insert(Arc::as_ptr(&arc), "meta");
+
+    let (_, old) = get_mut_drop_weak_with_old(&mut arc).unwrap();
+    assert_eq!(old, None);
+
+    let _weak = Arc::downgrade(&arc);
+    let (value, old) = get_mut_drop_weak_with_old(&mut arc).unwrap();
+    *value += 1;
+    let old = old.unwrap();
+    let meta = side_table.remove(&old).unwrap();
+    side_table.insert(Arc::as_ptr(&arc), meta);
+
+    assert_ne!(old, Arc::as_ptr(&arc));
+    assert_eq!(side_table.get(&Arc::as_ptr(&arc)), Some(&"meta"));
+}
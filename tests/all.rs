@@ -0,0 +1,122 @@
+use std::{rc::Rc, sync::Arc};
+
+use get_mut_drop_weak::{PointerTuple, get_many_mut, get_mut_all, get_mut_drop_weak2};
+
+#[test]
+fn test_pair_both_replaced() {
+    let mut a = Arc::new(1);
+    let mut b = Rc::new(String::from("b"));
+    let weak_a = Arc::downgrade(&a);
+    let weak_b = Rc::downgrade(&b);
+
+    let (x, y) = get_mut_drop_weak2(&mut a, &mut b).ok().unwrap();
+    *x += 1;
+    y.push('!');
+
+    assert_eq!(*a, 2);
+    assert_eq!(*b, "b!");
+    assert!(weak_a.upgrade().is_none());
+    assert!(weak_b.upgrade().is_none());
+}
+
+#[test]
+fn test_pair_second_shared_leaves_first_untouched() {
+    let mut a = Arc::new(1);
+    let mut b = Arc::new(2);
+    let weak_a = Arc::downgrade(&a);
+    let _other_b = Arc::clone(&b);
+
+    let (a_back, b_back) = get_mut_drop_weak2(&mut a, &mut b).err().unwrap();
+    assert_eq!(**a_back, 1);
+    assert_eq!(**b_back, 2);
+
+    // The first pointer's weak pointers survive.
+    assert_eq!(weak_a.upgrade().as_deref(), Some(&1));
+    assert!(Arc::ptr_eq(&weak_a.upgrade().unwrap(), &a));
+}
+
+#[test]
+fn test_pair_first_shared() {
+    let mut a = Arc::new(1);
+    let mut b = Arc::new(2);
+    let _other_a = Arc::clone(&a);
+    let weak_b = Arc::downgrade(&b);
+
+    assert!(get_mut_drop_weak2(&mut a, &mut b).is_err());
+    assert!(weak_b.upgrade().is_some());
+}
+
+#[test]
+fn test_pair_unique_kept_in_place() {
+    let mut a = Arc::new(1);
+    let mut b = Arc::new(2);
+    let (pa, pb) = (Arc::as_ptr(&a), Arc::as_ptr(&b));
+
+    let (x, y) = get_mut_drop_weak2(&mut a, &mut b).ok().unwrap();
+    std::mem::swap(x, y);
+
+    assert_eq!((*a, *b), (2, 1));
+    assert_eq!((Arc::as_ptr(&a), Arc::as_ptr(&b)), (pa, pb));
+}
+
+#[test]
+fn test_all_macro_three() {
+    let mut a = Arc::new(1);
+    let mut b = Rc::new(2u8);
+    let mut c = Arc::new(String::from("c"));
+    let weak_c = Arc::downgrade(&c);
+
+    let (x, y, z) = get_mut_all!(&mut a, &mut b, &mut c).ok().unwrap();
+    *x += 1;
+    *y += 1;
+    z.push('!');
+
+    assert_eq!((*a, *b, c.as_str()), (2, 3, "c!"));
+    assert!(weak_c.upgrade().is_none());
+}
+
+#[test]
+fn test_all_macro_last_shared() {
+    let mut a = Arc::new(1);
+    let mut b = Arc::new(2);
+    let mut c = Arc::new(3);
+    let weak_a = Arc::downgrade(&a);
+    let _other_c = Arc::clone(&c);
+
+    assert!(get_mut_all!(&mut a, &mut b, &mut c,).is_err());
+    assert!(weak_a.upgrade().is_some());
+}
+
+#[test]
+fn test_all_tuple_single() {
+    let mut a = Arc::new(1);
+    let (x,) = (&mut a,).get_mut_drop_weak_all().ok().unwrap();
+    *x = 5;
+    assert_eq!(*a, 5);
+}
+
+#[test]
+fn test_get_many_mut_all_unique() {
+    let mut arcs = [Arc::new(1), Arc::new(2)];
+    for value in get_many_mut(&mut arcs).unwrap() {
+        *value *= 10;
+    }
+    assert_eq!((*arcs[0], *arcs[1]), (10, 20));
+}
+
+#[test]
+fn test_get_many_mut_never_replaces() {
+    let mut arcs = [Arc::new(1), Arc::new(2)];
+    let weak = Arc::downgrade(&arcs[1]);
+
+    assert!(get_many_mut(&mut arcs).is_none());
+    assert!(weak.upgrade().is_some());
+}
+
+#[test]
+#[should_panic = "pointer was made exclusive"]
+fn test_private_exclusive_checks_counts() {
+    let arc = Arc::new(1);
+    let mut other = Arc::clone(&arc);
+    get_mut_drop_weak::__private::exclusive(&mut other);
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "nightly-allocator")]
+#![feature(allocator_api)]
+
+use std::alloc::{AllocError, Allocator, Global, Layout};
+use std::cell::Cell;
+use std::ptr::NonNull;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use get_mut_drop_weak::{ReplacementCache, get_mut_drop_weak, get_mut_drop_weak_cached};
+
+#[derive(Clone, Copy)]
+struct CountingAlloc<'a>(&'a AtomicUsize);
+
+unsafe impl Allocator for CountingAlloc<'_> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+#[test]
+fn test_replacement_uses_original_allocator() {
+    let allocations = AtomicUsize::new(0);
+    let mut arc = Arc::new_in(vec![1, 2], CountingAlloc(&allocations));
+    let weak = Arc::downgrade(&arc);
+    assert_eq!(allocations.load(Ordering::SeqCst), 1);
+
+    get_mut_drop_weak(&mut arc).unwrap().push(3);
+
+    assert_eq!(*arc, vec![1, 2, 3]);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(allocations.load(Ordering::SeqCst), 2);
+}
+
+static ALLOCATIONS: [AtomicUsize; 2] = [const { AtomicUsize::new(0) }; 2];
+
+thread_local! {
+    static BEFORE_ALLOC: Cell<Option<Box<dyn FnOnce()>>> = const { Cell::new(None) };
+}
+
+/// Counts allocations in `ALLOCATIONS[id]`, after running the hook in
+/// `BEFORE_ALLOC`, if any.
+#[derive(Clone, Copy)]
+struct HookedAlloc(usize);
+
+unsafe impl Allocator for HookedAlloc {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if let Some(hook) = BEFORE_ALLOC.take() {
+            hook();
+        }
+        ALLOCATIONS[self.0].fetch_add(1, Ordering::SeqCst);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+#[test]
+fn test_cache_keeps_replacements_in_their_allocator() {
+    let mut cache = ReplacementCache::new();
+    let mut arc = Arc::new_in(0, HookedAlloc(0));
+    let weak = Arc::downgrade(&arc);
+    let upgraded = Rc::new(Cell::new(None));
+    // Upgrade the weak pointer while the replacement is being allocated, so
+    // that the replacement loses the race and stays in the cache.
+    BEFORE_ALLOC.set(Some(Box::new({
+        let upgraded = Rc::clone(&upgraded);
+        move || upgraded.set(weak.upgrade())
+    })));
+    get_mut_drop_weak_cached(&mut arc, &mut cache).unwrap_err();
+    assert!(cache.is_allocated());
+    drop(upgraded.take());
+
+    // The cached allocation belongs to another allocator, so it is freed and
+    // the replacement is allocated in this pointer's allocator.
+    let mut other = Arc::new_in(1, HookedAlloc(1));
+    let _weak = Arc::downgrade(&other);
+    *get_mut_drop_weak_cached(&mut other, &mut cache).unwrap() += 1;
+    assert_eq!(ALLOCATIONS[1].load(Ordering::SeqCst), 2);
+    assert!(!cache.is_allocated());
+}
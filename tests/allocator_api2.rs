@@ -0,0 +1,84 @@
+#![cfg(feature = "allocator-api2")]
+
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+
+use allocator_api2::alloc::{AllocError, Allocator, Global, Layout};
+use get_mut_drop_weak::allocator_api2::{AllocatorArc, get_mut_drop_weak_in};
+
+// Stand-in for an allocator-aware Arc fork: records which allocator the
+// replacement was requested in.
+#[derive(Debug)]
+struct PoolArc<T>(Arc<T>);
+
+static REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Copy)]
+struct Pool;
+
+unsafe impl Allocator for Pool {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+unsafe impl<T> AllocatorArc<T> for PoolArc<T> {
+    type Allocator = Pool;
+    type Uninit = Arc<MaybeUninit<T>>;
+
+    fn as_ptr(this: &Self) -> *const T {
+        Arc::as_ptr(&this.0)
+    }
+
+    fn strong_count(this: &Self) -> usize {
+        Arc::strong_count(&this.0)
+    }
+
+    fn weak_count(this: &Self) -> usize {
+        Arc::weak_count(&this.0)
+    }
+
+    fn is_unique(this: &mut Self) -> bool {
+        Arc::get_mut(&mut this.0).is_some()
+    }
+
+    fn try_unwrap(this: Self) -> Result<T, Self> {
+        Arc::try_unwrap(this.0).map_err(PoolArc)
+    }
+
+    fn new_uninit_in(_alloc: Self::Allocator) -> Self::Uninit {
+        REQUESTS.fetch_add(1, Ordering::SeqCst);
+        Arc::new_uninit()
+    }
+
+    unsafe fn init(mut uninit: Self::Uninit, value: T) -> Self {
+        Arc::get_mut(&mut uninit).unwrap().write(value);
+        PoolArc(unsafe { uninit.assume_init() })
+    }
+}
+
+#[test]
+fn test_replacement_requested_in_supplied_allocator() {
+    let mut arc = PoolArc(Arc::new(5));
+
+    // No weak pointers: no replacement, allocator unused.
+    *get_mut_drop_weak_in(&mut arc, Pool).unwrap() += 1;
+    assert_eq!(REQUESTS.load(Ordering::SeqCst), 0);
+
+    let weak: Weak<i32> = Arc::downgrade(&arc.0);
+    *get_mut_drop_weak_in(&mut arc, Pool).unwrap() += 1;
+    assert_eq!(REQUESTS.load(Ordering::SeqCst), 1);
+    assert_eq!(*arc.0, 7);
+    assert!(weak.upgrade().is_none());
+
+    let other = PoolArc(Arc::clone(&arc.0));
+    get_mut_drop_weak_in(&mut arc, Pool).unwrap_err();
+    assert_eq!(REQUESTS.load(Ordering::SeqCst), 1);
+    drop(other);
+}
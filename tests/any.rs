@@ -0,0 +1,38 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use get_mut_drop_weak::get_mut_drop_weak_any;
+
+#[test]
+fn test_any_weak_shared_drops_weak_success() {
+    let mut arc: Arc<dyn Any + Send + Sync> = Arc::new(vec![1, 2]);
+    let weak = Arc::downgrade(&arc);
+    let original_ptr = Arc::as_ptr(&arc).cast::<()>();
+
+    get_mut_drop_weak_any::<Vec<i32>>(&mut arc).unwrap().push(3);
+
+    assert_eq!(arc.downcast_ref::<Vec<i32>>(), Some(&vec![1, 2, 3]));
+    assert_ne!(Arc::as_ptr(&arc).cast::<()>(), original_ptr);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_any_wrong_type_untouched() {
+    let mut arc: Arc<dyn Any + Send + Sync> = Arc::new(5u8);
+    let weak = Arc::downgrade(&arc);
+
+    get_mut_drop_weak_any::<u16>(&mut arc).unwrap_err();
+    assert!(weak.upgrade().is_some());
+
+    *get_mut_drop_weak_any::<u8>(&mut arc).unwrap() += 1;
+    assert_eq!(arc.downcast_ref::<u8>(), Some(&6));
+}
+
+#[test]
+fn test_any_strong_shared_no_mut() {
+    let mut arc: Arc<dyn Any + Send + Sync> = Arc::new(String::new());
+    let other = Arc::clone(&arc);
+
+    get_mut_drop_weak_any::<String>(&mut arc).unwrap_err();
+    assert!(Arc::ptr_eq(&arc, &other));
+}
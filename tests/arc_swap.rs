@@ -0,0 +1,55 @@
+#![cfg(feature = "arc-swap")]
+
+use std::{
+    sync::{Arc, Weak},
+    thread,
+};
+
+use arc_swap::ArcSwap;
+use get_mut_drop_weak::rcu_mut_drop_weak;
+
+#[test]
+fn test_rcu_mut_drop_weak() {
+    let swap = ArcSwap::from_pointee(vec![1]);
+    let weak = Arc::downgrade(&swap.load_full());
+
+    let len = rcu_mut_drop_weak(&swap, |value| {
+        value.push(2);
+        value.len()
+    });
+
+    assert_eq!(len, 2);
+    assert_eq!(**swap.load(), [1, 2]);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_rcu_mut_drop_weak_leaves_guards_intact() {
+    let swap = ArcSwap::from_pointee(vec![1]);
+    let guard = swap.load();
+    // The guard doesn't show up in the strong count, so the value only looks
+    // shared between the swap and a loaded copy.
+    assert_eq!(Arc::strong_count(&swap.load_full()), 2);
+
+    rcu_mut_drop_weak(&swap, |value| value.push(2));
+
+    assert_eq!(**guard, [1]);
+    assert_eq!(**swap.load(), [1, 2]);
+}
+
+#[test]
+fn test_rcu_mut_drop_weak_concurrent() {
+    let swap = ArcSwap::from_pointee(0);
+    let weak: Weak<i32> = Arc::downgrade(&swap.load_full());
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..100 {
+                    rcu_mut_drop_weak(&swap, |value| *value += 1);
+                }
+            });
+        }
+    });
+    assert_eq!(**swap.load(), 400);
+    assert!(weak.upgrade().is_none());
+}
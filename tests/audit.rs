@@ -0,0 +1,39 @@
+#![cfg(feature = "weak-audit")]
+
+use get_mut_drop_weak::AuditedArc;
+
+fn downgrade_here(arc: &AuditedArc<i32>) -> get_mut_drop_weak::AuditedWeak<i32> {
+    AuditedArc::downgrade(arc)
+}
+
+#[test]
+fn test_audit_lists_live_origins() {
+    let mut arc = AuditedArc::new(1);
+    let first = downgrade_here(&arc);
+    let second = AuditedArc::downgrade(&arc);
+    let copy = second.clone();
+
+    assert_eq!(AuditedArc::live_origins(&arc).len(), 2);
+    assert!(first.origin().to_string().contains("downgrade_here"));
+
+    drop(first);
+    assert_eq!(AuditedArc::live_origins(&arc).len(), 1);
+    drop(second);
+    // The clone keeps the origin alive.
+    assert_eq!(AuditedArc::live_origins(&arc).len(), 1);
+
+    *arc.get_mut_drop_weak().unwrap() += 1;
+
+    assert_eq!(*arc, 2);
+    assert!(copy.upgrade().is_none());
+    assert!(AuditedArc::live_origins(&arc).is_empty());
+}
+
+#[test]
+fn test_audit_upgrade_shares_origins() {
+    let arc = AuditedArc::new(1);
+    let weak = AuditedArc::downgrade(&arc);
+    let upgraded = weak.upgrade().unwrap();
+    let _other = AuditedArc::downgrade(&upgraded);
+    assert_eq!(AuditedArc::live_origins(&arc).len(), 2);
+}
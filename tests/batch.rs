@@ -0,0 +1,82 @@
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[cfg(feature = "std")]
+use get_mut_drop_weak::make_unique_all_drop_weak;
+use get_mut_drop_weak::{BatchOutcome, first_exclusive, get_mut_drop_weak_batch};
+
+#[test]
+fn test_batch_outcomes() {
+    let mut arcs = vec![Arc::new(0), Arc::new(1), Arc::new(2)];
+    let weak = Arc::downgrade(&arcs[1]);
+    let other = Arc::clone(&arcs[2]);
+
+    let outcomes = get_mut_drop_weak_batch(&mut arcs);
+    assert_eq!(
+        outcomes,
+        [
+            BatchOutcome::Exclusive,
+            BatchOutcome::Replaced,
+            BatchOutcome::Shared
+        ]
+    );
+    assert!(weak.upgrade().is_none());
+    assert!(Arc::ptr_eq(&arcs[2], &other));
+
+    for (arc, outcome) in arcs.iter_mut().zip(outcomes) {
+        if outcome != BatchOutcome::Shared {
+            *Arc::get_mut(arc).unwrap() += 10;
+        }
+    }
+    assert_eq!(
+        arcs.iter().map(|arc| **arc).collect::<Vec<_>>(),
+        [10, 11, 2]
+    );
+}
+
+#[test]
+fn test_batch_empty() {
+    let mut arcs: Vec<Arc<u8>> = Vec::new();
+    assert!(get_mut_drop_weak_batch(&mut arcs).is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_make_unique_all_reports_shared_keys() {
+    let mut map = HashMap::new();
+    map.insert("unique", Arc::new(0));
+    map.insert("weak", Arc::new(1));
+    map.insert("shared", Arc::new(2));
+    let weak = Arc::downgrade(&map["weak"]);
+    let _other = Arc::clone(&map["shared"]);
+
+    assert_eq!(make_unique_all_drop_weak(&mut map), ["shared"]);
+    assert!(weak.upgrade().is_none());
+    for (key, arc) in map.iter_mut() {
+        if *key != "shared" {
+            *Arc::get_mut(arc).unwrap() += 10;
+        }
+    }
+    assert_eq!(*map["weak"], 11);
+}
+
+#[test]
+fn test_first_exclusive_skips_shared() {
+    let mut free_list = vec![Arc::new(vec![0u8; 4]), Arc::new(vec![1u8; 4])];
+    let in_use = Arc::clone(&free_list[0]);
+    let weak = Arc::downgrade(&free_list[1]);
+
+    first_exclusive(&mut free_list).unwrap().clear();
+
+    assert_eq!(*in_use, [0; 4]);
+    assert!(free_list[1].is_empty());
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_first_exclusive_none() {
+    let mut free_list = [Arc::new(0)];
+    let _in_use = Arc::clone(&free_list[0]);
+    assert!(first_exclusive(free_list.iter_mut()).is_none());
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "std")]
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use get_mut_drop_weak::{ArcGetMutExt, Timeout, get_mut_drop_weak_blocking, with_exclusive};
+
+#[test]
+fn test_blocking_waits_for_reader() {
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+    let reader = Arc::clone(&arc);
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        drop(reader);
+    });
+
+    *get_mut_drop_weak_blocking(&mut arc, Duration::from_secs(60)).unwrap() += 1;
+
+    handle.join().unwrap();
+    assert_eq!(*arc, 2);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_blocking_times_out() {
+    let mut arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+
+    arc.get_mut_drop_weak_blocking(Duration::from_millis(10))
+        .unwrap_err();
+    assert!(Arc::ptr_eq(&arc, &other));
+}
+
+#[test]
+fn test_blocking_unbounded_timeout() {
+    let mut arc = Arc::new(1);
+    let _weak = Arc::downgrade(&arc);
+    *get_mut_drop_weak_blocking(&mut arc, Duration::MAX).unwrap() += 1;
+    assert_eq!(*arc, 2);
+}
+
+#[test]
+fn test_with_exclusive_unparked_by_reader() {
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+    let reader = Arc::clone(&arc);
+    let writer = thread::current();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        drop(reader);
+        writer.unpark();
+    });
+
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let old = with_exclusive(&mut arc, deadline, |value| std::mem::replace(value, 2));
+
+    handle.join().unwrap();
+    assert_eq!(old, Ok(1));
+    assert_eq!(*arc, 2);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_with_exclusive_times_out() {
+    let mut arc = Arc::new(1);
+    let _other = Arc::clone(&arc);
+
+    let deadline = Instant::now() + Duration::from_millis(10);
+    let result = arc.with_exclusive(deadline, |_| unreachable!());
+
+    assert_eq!(result, Err::<(), _>(Timeout));
+}
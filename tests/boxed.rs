@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::get_mut_drop_weak_boxed;
+
+#[test]
+fn test_boxed_weak_shared_keeps_value_in_place() {
+    let mut arc = Arc::new(Box::new([0u8; 1 << 16]));
+    let weak = Arc::downgrade(&arc);
+    let original_ptr = Arc::as_ptr(&arc);
+    let boxed_ptr = arc.as_ptr();
+
+    get_mut_drop_weak_boxed(&mut arc).unwrap()[0] = 1;
+
+    assert_eq!(arc[0], 1);
+    assert_ne!(Arc::as_ptr(&arc), original_ptr);
+    assert_eq!(arc.as_ptr(), boxed_ptr);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_boxed_unsized() {
+    let mut arc: Arc<Box<[u8]>> = Arc::new(Box::new([1, 2, 3]));
+    let weak = Arc::downgrade(&arc);
+
+    get_mut_drop_weak_boxed(&mut arc).unwrap().reverse();
+
+    assert_eq!(**arc, [3, 2, 1]);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_boxed_strong_shared_no_mut() {
+    let mut arc = Arc::new(Box::new(1));
+    let other = Arc::clone(&arc);
+
+    get_mut_drop_weak_boxed(&mut arc).unwrap_err();
+    assert!(Arc::ptr_eq(&arc, &other));
+}
@@ -0,0 +1,50 @@
+use std::{sync::Arc, thread};
+
+use get_mut_drop_weak::{ArcGetMutExt, ReplacementCache, get_mut_drop_weak_cached};
+
+#[test]
+fn test_cached_weak_shared_drops_weak() {
+    let mut cache = ReplacementCache::new();
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+
+    *get_mut_drop_weak_cached(&mut arc, &mut cache).unwrap() += 1;
+
+    assert_eq!(*arc, 2);
+    assert!(weak.upgrade().is_none());
+    assert!(!cache.is_allocated());
+}
+
+#[test]
+fn test_cached_strong_shared() {
+    let mut cache = ReplacementCache::new();
+    let mut arc = Arc::new(1);
+    let _other = Arc::clone(&arc);
+    arc.get_mut_drop_weak_cached(&mut cache).unwrap_err();
+    assert!(!cache.is_allocated());
+}
+
+#[test]
+fn test_cached_lost_race_keeps_allocation() {
+    let mut cache = ReplacementCache::new();
+    for _ in 0..100 {
+        let mut arc = Arc::new(0);
+        let weak = Arc::downgrade(&arc);
+        let upgrader = {
+            let weak = weak.clone();
+            thread::spawn(move || weak.upgrade())
+        };
+        let result = get_mut_drop_weak_cached(&mut arc, &mut cache).is_ok();
+        let upgraded = upgrader.join().unwrap();
+        if !result && Arc::strong_count(&arc) == 2 && upgraded.is_some() {
+            // Either refused before allocating, or kept the allocation for
+            // this retry, which still has to replace the pointer.
+            drop(upgraded);
+            *get_mut_drop_weak_cached(&mut arc, &mut cache).unwrap() += 1;
+            assert!(!cache.is_allocated());
+            assert!(weak.upgrade().is_none());
+        }
+    }
+    cache.clear();
+    assert!(!cache.is_allocated());
+}
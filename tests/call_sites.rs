@@ -0,0 +1,67 @@
+#![cfg(feature = "call-sites")]
+
+use std::sync::Arc;
+
+use get_mut_drop_weak::{
+    ArcGetMutExt, ArcPool, CALL_SITE_CAPACITY, clear_orphaning_call_sites, get_mut_drop_weak,
+    get_mut_drop_weak_pooled, make_mut_drop_weak, orphaning_call_sites, replace_drop_weak,
+    take_drop_weak,
+};
+
+// The call sites are global, so everything is checked in a single test.
+#[test]
+fn test_call_sites() {
+    clear_orphaning_call_sites();
+
+    let mut arc = Arc::new(1);
+    let _weak = Arc::downgrade(&arc);
+    get_mut_drop_weak(&mut arc).unwrap();
+    let first = line!() - 1;
+
+    // Already exclusive: nothing is orphaned.
+    get_mut_drop_weak(&mut arc).unwrap();
+
+    let _weak = Arc::downgrade(&arc);
+    *make_mut_drop_weak(&mut arc) += 1;
+    let second = line!() - 1;
+
+    let _weak = Arc::downgrade(&arc);
+    arc.get_mut_drop_weak_retry(3).unwrap();
+    let third = line!() - 1;
+
+    let _weak = Arc::downgrade(&arc);
+    replace_drop_weak(&mut arc, 5).unwrap();
+    let fourth = line!() - 1;
+
+    let _weak = Arc::downgrade(&arc);
+    take_drop_weak(&mut arc).unwrap();
+    let fifth = line!() - 1;
+
+    let pool = ArcPool::new(1);
+    let _weak = Arc::downgrade(&arc);
+    get_mut_drop_weak_pooled(&mut arc, &pool).unwrap();
+    let sixth = line!() - 1;
+
+    let sites = orphaning_call_sites();
+    let lines: Vec<_> = sites.iter().map(|site| site.line()).collect();
+    assert_eq!(lines, [first, second, third, fourth, fifth, sixth]);
+    assert!(
+        sites
+            .iter()
+            .all(|site| site.file().ends_with("call_sites.rs"))
+    );
+
+    // Only the most recent call sites are kept.
+    let mut last = 0;
+    for _ in 0..CALL_SITE_CAPACITY {
+        let _weak = Arc::downgrade(&arc);
+        get_mut_drop_weak(&mut arc).unwrap();
+        last = line!() - 1;
+    }
+    let sites = orphaning_call_sites();
+    assert_eq!(sites.len(), CALL_SITE_CAPACITY);
+    assert!(sites.iter().all(|site| site.line() == last));
+
+    clear_orphaning_call_sites();
+    assert!(orphaning_call_sites().is_empty());
+}
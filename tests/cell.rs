@@ -0,0 +1,55 @@
+use std::{sync::Arc, thread};
+
+use get_mut_drop_weak::ArcCell;
+
+#[test]
+fn test_cell_with_mut_drop_weak() {
+    let cell = ArcCell::new(1);
+    let weak = Arc::downgrade(&cell.load());
+
+    assert_eq!(cell.with_mut_drop_weak(|value| *value += 1), Some(()));
+
+    assert_eq!(*cell.load(), 2);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_cell_loaded_is_strongly_shared() {
+    let cell = ArcCell::new(1);
+    let loaded = cell.load();
+    assert_eq!(cell.with_mut_drop_weak(|value| *value += 1), None);
+    drop(loaded);
+    assert_eq!(cell.with_mut_drop_weak(|value| *value), Some(1));
+}
+
+#[test]
+fn test_cell_debug_while_locked() {
+    let cell = ArcCell::new(1);
+    assert_eq!(format!("{cell:?}"), "ArcCell(1)");
+    assert_eq!(
+        cell.with_mut_drop_weak(|_| format!("{cell:?}")).as_deref(),
+        Some("ArcCell(<locked>)")
+    );
+}
+
+#[test]
+fn test_cell_swap() {
+    let cell = ArcCell::new(1);
+    assert_eq!(*cell.swap(Arc::new(2)), 1);
+    assert_eq!(*cell.into_arc(), 2);
+}
+
+#[test]
+fn test_cell_shared_between_threads() {
+    let cell = ArcCell::new(0);
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..100 {
+                    cell.with_mut_drop_weak(|value| *value += 1).unwrap();
+                }
+            });
+        }
+    });
+    assert_eq!(*cell.load(), 400);
+}
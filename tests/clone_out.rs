@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::{MutOrClone, get_mut_or_clone_out};
+
+#[test]
+fn test_clone_out_unique_is_mut() {
+    let mut arc = Arc::new(vec![1]);
+    match get_mut_or_clone_out(&mut arc) {
+        MutOrClone::Mut(value) => value.push(2),
+        MutOrClone::Cloned(_) => panic!("unique"),
+    }
+    assert_eq!(*arc, [1, 2]);
+}
+
+#[test]
+fn test_clone_out_publish_after_reader_drops() {
+    let mut arc = Arc::new(vec![1]);
+    let reader = Arc::clone(&arc);
+
+    let MutOrClone::Cloned(mut copy) = get_mut_or_clone_out(&mut arc) else {
+        panic!("shared");
+    };
+    copy.push(2);
+    assert_eq!(**copy.ptr(), [1]);
+
+    // Still shared: the copy comes back.
+    let copy = copy.publish().unwrap_err();
+    drop(reader);
+
+    let old = copy.publish().unwrap();
+    assert_eq!(old, [1]);
+    assert_eq!(*arc, [1, 2]);
+}
+
+#[test]
+fn test_clone_out_publish_detached() {
+    let mut arc = Arc::new(String::from("a"));
+    let reader = Arc::clone(&arc);
+
+    let MutOrClone::Cloned(mut copy) = get_mut_or_clone_out(&mut arc) else {
+        panic!("shared");
+    };
+    copy.push('b');
+    copy.publish_detached().push('c');
+
+    assert_eq!(*arc, "abc");
+    assert_eq!(*reader, "a");
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::clone_replace_drop_weak;
+
+#[test]
+fn test_clone_replace_weak_shared_drops_weak() {
+    let mut arc = Arc::new(String::from("a"));
+    let weak = Arc::downgrade(&arc);
+    let original_ptr = Arc::as_ptr(&arc);
+
+    clone_replace_drop_weak(&mut arc).unwrap().push('b');
+
+    assert_eq!(*arc, "ab");
+    assert_ne!(Arc::as_ptr(&arc), original_ptr);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_clone_replace_exclusive_not_replaced() {
+    let mut arc = Arc::new(1);
+    let original_ptr = Arc::as_ptr(&arc);
+
+    *clone_replace_drop_weak(&mut arc).unwrap() += 1;
+
+    assert_eq!(*arc, 2);
+    assert_eq!(Arc::as_ptr(&arc), original_ptr);
+}
+
+#[test]
+fn test_clone_replace_strong_shared_no_mut() {
+    let mut arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+
+    clone_replace_drop_weak(&mut arc).unwrap_err();
+    assert!(Arc::ptr_eq(&arc, &other));
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::{ArcGetMutExt, get_mut_drop_weak_copy};
+
+#[test]
+fn test_copy_weak_shared_drops_weak() {
+    let mut arc = Arc::new([1u8; 16]);
+    let weak = Arc::downgrade(&arc);
+    let original_ptr = Arc::as_ptr(&arc);
+
+    get_mut_drop_weak_copy(&mut arc).unwrap()[0] = 2;
+
+    assert_eq!(arc[0], 2);
+    assert_ne!(Arc::as_ptr(&arc), original_ptr);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_copy_exclusive_not_replaced() {
+    let mut arc = Arc::new(1);
+    let original_ptr = Arc::as_ptr(&arc);
+
+    *arc.get_mut_drop_weak_copy().unwrap() += 1;
+
+    assert_eq!(*arc, 2);
+    assert_eq!(Arc::as_ptr(&arc), original_ptr);
+}
+
+#[test]
+fn test_copy_strong_shared_no_mut() {
+    let mut arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+
+    get_mut_drop_weak_copy(&mut arc).unwrap_err();
+    assert!(Arc::ptr_eq(&arc, &other));
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "derive")]
+
+use std::{rc::Rc, sync::Arc};
+
+use get_mut_drop_weak::{FailureReason, GetMutAll};
+
+#[derive(GetMutAll)]
+struct Pair {
+    left: Arc<String>,
+    right: Rc<u32>,
+}
+
+#[derive(GetMutAll)]
+pub struct Generic<T: Clone> {
+    pub value: Arc<T>,
+}
+
+#[test]
+fn test_derive_replaces_all() {
+    let mut pair = Pair {
+        left: Arc::new(String::from("l")),
+        right: Rc::new(1),
+    };
+    let weak_left = Arc::downgrade(&pair.left);
+    let weak_right = Rc::downgrade(&pair.right);
+
+    let PairMut { left, right } = pair.get_mut_all().unwrap();
+    left.push('!');
+    *right += 1;
+
+    assert_eq!(*pair.left, "l!");
+    assert_eq!(*pair.right, 2);
+    assert!(weak_left.upgrade().is_none());
+    assert!(weak_right.upgrade().is_none());
+}
+
+#[test]
+fn test_derive_names_blocking_field() {
+    let mut pair = Pair {
+        left: Arc::new(String::new()),
+        right: Rc::new(1),
+    };
+    let weak_left = Arc::downgrade(&pair.left);
+    let _other = Rc::clone(&pair.right);
+
+    let error = pair.get_mut_all().err().unwrap();
+    assert_eq!(error.field(), "right");
+    assert_eq!(error.reason(), FailureReason::StronglyShared);
+    assert_eq!(error.to_string(), "field `right`: value is strongly shared");
+
+    // Nothing was touched.
+    assert!(weak_left.upgrade().is_some());
+}
+
+#[test]
+fn test_derive_generic() {
+    let mut generic = Generic {
+        value: Arc::new(vec![1]),
+    };
+    generic.get_mut_all().unwrap().value.push(2);
+    assert_eq!(*generic.value, [1, 2]);
+}
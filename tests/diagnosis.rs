@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::{Exclusivity, explain};
+
+#[test]
+fn test_explain() {
+    let arc = Arc::new(1);
+    let diagnosis = explain(&arc);
+    assert!(!diagnosis.has_weak());
+    assert!(!diagnosis.needs_replacement());
+    assert_eq!(
+        diagnosis.to_string(),
+        "strong = 1, weak = 0: exclusive, no replacement needed"
+    );
+
+    let _weak = Arc::downgrade(&arc);
+    let diagnosis = explain(&arc);
+    assert!(diagnosis.has_weak());
+    assert!(diagnosis.needs_replacement());
+    assert_eq!(
+        diagnosis.to_string(),
+        "strong = 1, weak = 1: only weakly shared, replacement needed, which would orphan 1 weak pointer(s)"
+    );
+
+    let _other = Arc::clone(&arc);
+    let diagnosis = explain(&arc);
+    assert!(!diagnosis.needs_replacement());
+    assert_eq!(diagnosis.exclusivity(), Exclusivity::Shared { strong: 2 });
+    assert_eq!(
+        diagnosis.to_string(),
+        "strong = 2, weak = 1: strongly shared by 2 pointers, exclusive access impossible until 1 other strong pointer(s) are dropped"
+    );
+}
@@ -0,0 +1,43 @@
+use std::ffi::CStr;
+#[cfg(feature = "std")]
+use std::ffi::OsStr;
+#[cfg(feature = "std")]
+use std::path::Path;
+use std::sync::Arc;
+
+use get_mut_drop_weak::get_mut_drop_weak_unsized;
+
+#[cfg(feature = "std")]
+#[test]
+fn test_os_str_weak_shared_rebuilds() {
+    let mut arc: Arc<OsStr> = Arc::from(OsStr::new("hello"));
+    let weak = Arc::downgrade(&arc);
+    let original_ptr = Arc::as_ptr(&arc);
+
+    get_mut_drop_weak_unsized(&mut arc)
+        .unwrap()
+        .make_ascii_uppercase();
+
+    assert_eq!(&*arc, OsStr::new("HELLO"));
+    assert_ne!(Arc::as_ptr(&arc), original_ptr);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_cstr_exclusive_not_rebuilt() {
+    let mut arc: Arc<CStr> = Arc::from(c"abc");
+    let original_ptr = Arc::as_ptr(&arc);
+
+    get_mut_drop_weak_unsized(&mut arc).unwrap();
+    assert_eq!(Arc::as_ptr(&arc), original_ptr);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_path_strong_shared_no_mut() {
+    let mut arc: Arc<Path> = Arc::from(Path::new("/tmp"));
+    let other = Arc::clone(&arc);
+
+    get_mut_drop_weak_unsized(&mut arc).unwrap_err();
+    assert!(Arc::ptr_eq(&arc, &other));
+}
@@ -0,0 +1,55 @@
+use std::thread;
+
+use get_mut_drop_weak::{EpochArc, EpochWeak};
+
+#[test]
+fn test_epoch_invalidates_in_place() {
+    let mut arc = EpochArc::new(1);
+    let weak = EpochArc::downgrade(&arc);
+    let weak2 = weak.clone();
+    let original_ptr: *const i32 = &*arc;
+
+    *arc.get_mut_drop_weak().unwrap() += 1;
+
+    assert_eq!(*arc, 2);
+    assert_eq!(&*arc as *const i32, original_ptr);
+    assert!(weak.upgrade().is_none());
+    assert!(weak2.upgrade().is_none());
+
+    // Handles created afterwards work.
+    let weak = EpochArc::downgrade(&arc);
+    assert!(EpochArc::ptr_eq(&weak.upgrade().unwrap(), &arc));
+}
+
+#[test]
+fn test_epoch_no_weak_keeps_generation() {
+    let mut arc = EpochArc::new(1);
+    *arc.get_mut_drop_weak().unwrap() += 1;
+    let weak = EpochArc::downgrade(&arc);
+    assert_eq!(*weak.upgrade().unwrap(), 2);
+}
+
+#[test]
+fn test_epoch_strong_shared() {
+    let mut arc = EpochArc::new(1);
+    let other = arc.clone();
+    assert!(arc.get_mut_drop_weak().is_none());
+    assert!(EpochArc::ptr_eq(&arc, &other));
+}
+
+#[test]
+fn test_epoch_concurrent_upgrades() {
+    let mut arc = EpochArc::new(0);
+    let weak: EpochWeak<i32> = EpochArc::downgrade(&arc);
+    let upgrader = thread::spawn(move || {
+        while let Some(arc) = weak.upgrade() {
+            assert!(*arc >= 0);
+        }
+    });
+
+    while arc.get_mut_drop_weak().is_none() {}
+    *arc.get_mut_drop_weak().unwrap() += 1;
+
+    upgrader.join().unwrap();
+    assert_eq!(*arc, 1);
+}
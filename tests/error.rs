@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::{ArcGetMutExt, FailureReason, get_mut_drop_weak_detailed};
+
+#[test]
+fn test_detailed_strong_shared_reports_counts() {
+    let mut arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+    let _weak = Arc::downgrade(&arc);
+
+    let err = get_mut_drop_weak_detailed(&mut arc).unwrap_err();
+    assert_eq!(err.reason(), FailureReason::StronglyShared);
+    assert_eq!(err.strong_count(), 2);
+    assert_eq!(err.weak_count(), 1);
+    assert_eq!(
+        err.to_string(),
+        "value is strongly shared (strong = 2, weak = 1)"
+    );
+    assert!(Arc::ptr_eq(err.into_inner(), &other));
+}
+
+#[test]
+fn test_detailed_weak_shared_success() {
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+
+    *arc.get_mut_drop_weak_detailed().unwrap() += 1;
+    assert_eq!(*arc, 2);
+    assert!(weak.upgrade().is_none());
+}
@@ -0,0 +1,24 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use get_mut_drop_weak::{Exclusivity, exclusivity};
+
+#[test]
+fn test_exclusivity() {
+    let arc = Arc::new(1);
+    assert_eq!(exclusivity(&arc), Exclusivity::Unique);
+
+    let weak = Arc::downgrade(&arc);
+    let _weak2 = weak.clone();
+    assert_eq!(exclusivity(&arc), Exclusivity::WeakOnly { weak: 2 });
+
+    let _other = Arc::clone(&arc);
+    assert_eq!(exclusivity(&arc), Exclusivity::Shared { strong: 2 });
+}
+
+#[test]
+fn test_exclusivity_rc() {
+    let rc = Rc::new(1);
+    let _weak = Rc::downgrade(&rc);
+    assert_eq!(exclusivity(&rc), Exclusivity::WeakOnly { weak: 1 });
+}
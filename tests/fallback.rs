@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::{DropWeakResultExt, get_mut_drop_weak};
+
+#[test]
+fn test_or_clone_strong_shared() {
+    let mut arc = Arc::new(String::from("a"));
+    let other = Arc::clone(&arc);
+
+    get_mut_drop_weak(&mut arc).or_clone().push('b');
+    assert_eq!(*arc, "ab");
+    assert_eq!(*other, "a");
+}
+
+#[test]
+fn test_or_default_strong_shared() {
+    let mut arc = Arc::new(vec![1, 2]);
+    let other = Arc::clone(&arc);
+
+    get_mut_drop_weak(&mut arc).or_default().push(3);
+    assert_eq!(*arc, [3]);
+    assert_eq!(*other, [1, 2]);
+}
+
+#[test]
+fn test_or_insert_with_not_called_on_success() {
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+
+    *get_mut_drop_weak(&mut arc).or_insert_with(|| panic!("fallback must not be called")) += 1;
+    assert_eq!(*arc, 2);
+    assert!(weak.upgrade().is_none());
+}
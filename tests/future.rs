@@ -0,0 +1,132 @@
+use std::future::Future;
+use std::pin::{Pin, pin};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::Duration;
+
+use get_mut_drop_weak::{get_mut_drop_weak_async, get_mut_drop_weak_async_with};
+
+struct Unpark(Thread);
+
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// A runtime's sleep, standing in as a thread that wakes the task once
+/// `duration` has passed.
+struct Sleep {
+    duration: Duration,
+    done: Option<Arc<AtomicBool>>,
+}
+
+fn sleep(duration: Duration) -> Sleep {
+    Sleep {
+        duration,
+        done: None,
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        match &self.done {
+            Some(done) if done.load(Ordering::Acquire) => Poll::Ready(()),
+            Some(_) => Poll::Pending,
+            None => {
+                let done = Arc::new(AtomicBool::new(false));
+                let (duration, waker) = (self.duration, cx.waker().clone());
+                let flag = Arc::clone(&done);
+                thread::spawn(move || {
+                    thread::sleep(duration);
+                    flag.store(true, Ordering::Release);
+                    waker.wake();
+                });
+                self.done = Some(done);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Polls `future` to completion, parking between polls until it is woken,
+/// and returns its output and the number of polls.
+fn block_on<F: Future>(future: F) -> (F::Output, usize) {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut polls = 1;
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return (output, polls);
+        }
+        polls += 1;
+        thread::park();
+    }
+}
+
+#[test]
+fn test_async_weak_shared_ready_immediately() {
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+
+    let (value, polls) = block_on(get_mut_drop_weak_async(&mut arc));
+    *value.unwrap() += 1;
+
+    assert_eq!(polls, 1);
+    assert_eq!(*arc, 2);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_async_waits_for_reader() {
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+    let reader = Arc::clone(&arc);
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        drop(reader);
+    });
+
+    let (value, polls) = block_on(get_mut_drop_weak_async(&mut arc));
+    *value.unwrap() += 1;
+
+    handle.join().unwrap();
+    assert!(polls > 1);
+    assert_eq!(*arc, 2);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_async_with_sleeps_while_strongly_shared() {
+    let mut arc = Arc::new(1);
+    let reader = Arc::clone(&arc);
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        drop(reader);
+    });
+
+    let (value, polls) = block_on(get_mut_drop_weak_async_with(&mut arc, sleep));
+    *value.unwrap() += 1;
+
+    handle.join().unwrap();
+    // Polling about once a millisecond, rather than on every turn of the executor.
+    assert!(polls < 100, "polled {polls} times");
+    assert_eq!(*arc, 2);
+}
+
+#[test]
+fn test_async_with_weak_shared_ready_immediately() {
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+
+    let (value, polls) = block_on(get_mut_drop_weak_async_with(&mut arc, sleep));
+    *value.unwrap() += 1;
+
+    assert_eq!(polls, 1);
+    assert!(weak.upgrade().is_none());
+}
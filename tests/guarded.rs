@@ -0,0 +1,38 @@
+use std::thread;
+
+use get_mut_drop_weak::GuardedArc;
+
+#[test]
+fn test_guarded_weak_shared_drops_weak() {
+    let mut arc = GuardedArc::new(1);
+    let weak = GuardedArc::downgrade(&arc);
+
+    *arc.get_mut_drop_weak().unwrap() += 1;
+
+    assert_eq!(*arc, 2);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_guarded_strong_shared() {
+    let mut arc = GuardedArc::new(1);
+    let other = arc.clone();
+    assert!(arc.get_mut_drop_weak().is_none());
+    assert!(GuardedArc::ptr_eq(&arc, &other));
+}
+
+#[test]
+fn test_guarded_never_loses_race() {
+    for _ in 0..100 {
+        let mut arc = GuardedArc::new(0);
+        let weak = GuardedArc::downgrade(&arc);
+        let upgrader = thread::spawn(move || while weak.upgrade().is_some() {});
+
+        // A successful upgrade briefly shares the pointer, so retry until
+        // the upgrader lets go. Every retry is then refused before allocating.
+        while arc.get_mut_drop_weak().is_none() {}
+        assert_eq!(*arc, 0);
+
+        upgrader.join().unwrap();
+    }
+}
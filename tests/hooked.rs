@@ -0,0 +1,64 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use get_mut_drop_weak::HookedArc;
+
+fn counter() -> (Arc<AtomicUsize>, impl Fn() + Send + Sync + 'static) {
+    let count = Arc::new(AtomicUsize::new(0));
+    let hook = {
+        let count = Arc::clone(&count);
+        move || {
+            count.fetch_add(1, Ordering::Relaxed);
+        }
+    };
+    (count, hook)
+}
+
+#[test]
+fn test_hooked_fires_on_replacement() {
+    let mut arc = HookedArc::new(1);
+    let (count, hook) = counter();
+    let weak = HookedArc::downgrade_with(&mut arc, hook);
+    let copy = weak.clone();
+
+    *arc.get_mut_drop_weak().unwrap() += 1;
+
+    assert_eq!(*arc, 2);
+    assert!(copy.upgrade().is_none());
+    // Once per handle, not per clone.
+    assert_eq!(count.load(Ordering::Relaxed), 1);
+
+    // Nothing left to fire.
+    *arc.get_mut_drop_weak().unwrap() += 1;
+    assert_eq!(count.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_hooked_dropped_handle_does_not_fire() {
+    let mut arc = HookedArc::new(1);
+    let (dropped_count, dropped_hook) = counter();
+    let (kept_count, kept_hook) = counter();
+    drop(HookedArc::downgrade_with(&mut arc, dropped_hook));
+    let _kept = HookedArc::downgrade_with(&mut arc, kept_hook);
+
+    arc.get_mut_drop_weak().unwrap();
+
+    assert_eq!(dropped_count.load(Ordering::Relaxed), 0);
+    assert_eq!(kept_count.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_hooked_strong_shared_does_not_fire() {
+    let mut arc = HookedArc::new(1);
+    let (count, hook) = counter();
+    let weak = HookedArc::downgrade_with(&mut arc, hook);
+    let other = weak.upgrade().unwrap();
+
+    assert!(arc.get_mut_drop_weak().is_none());
+    assert_eq!(count.load(Ordering::Relaxed), 0);
+    drop(other);
+    arc.get_mut_drop_weak().unwrap();
+    assert_eq!(count.load(Ordering::Relaxed), 1);
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "hybrid-rc")]
+
+use get_mut_drop_weak::get_mut_drop_weak;
+use hybrid_rc::{Arc, Rc};
+
+#[test]
+fn test_hybrid_arc_weak_shared_drops_weak_success() {
+    let mut arc = Arc::new(vec![1, 2]);
+    let weak = Arc::downgrade(&arc);
+    let original_ptr = Arc::as_ptr(&arc);
+
+    get_mut_drop_weak(&mut arc).unwrap().push(3);
+
+    assert_eq!(*arc, vec![1, 2, 3]);
+    assert_ne!(Arc::as_ptr(&arc), original_ptr);
+    assert!(weak.upgrade().is_err());
+}
+
+#[test]
+fn test_hybrid_rc_weak_shared_drops_weak_success() {
+    let mut rc = Rc::new(1);
+    let weak = Rc::downgrade(&rc);
+
+    *get_mut_drop_weak(&mut rc).unwrap() += 1;
+
+    assert_eq!(*rc, 2);
+    assert!(weak.upgrade_local().is_err());
+}
+
+#[test]
+fn test_hybrid_local_and_shared_no_mut() {
+    // A shared reference alongside the local one blocks exclusive access from either side.
+    let mut rc = Rc::new(1);
+    let mut arc = Rc::to_shared(&rc);
+
+    get_mut_drop_weak(&mut rc).unwrap_err();
+    get_mut_drop_weak(&mut arc).unwrap_err();
+    drop(arc);
+    get_mut_drop_weak(&mut rc).unwrap();
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "std")]
+
+use std::sync::Arc;
+
+use get_mut_drop_weak::Interner;
+
+#[test]
+fn test_intern_deduplicates() {
+    let mut interner = Interner::new();
+    let a = interner.intern("hello");
+    let b = interner.intern("hello");
+    let c = interner.intern("world");
+    assert!(Arc::ptr_eq(&a, &b));
+    assert!(!Arc::ptr_eq(&a, &c));
+    assert!(Arc::ptr_eq(&interner.get("hello").unwrap(), &a));
+    assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn test_intern_does_not_keep_alive() {
+    let mut interner = Interner::new();
+    drop(interner.intern("hello"));
+    assert!(interner.get("hello").is_none());
+    assert_eq!(interner.purge(), 1);
+    assert!(interner.is_empty());
+}
+
+#[test]
+fn test_intern_strongly_shared_get_mut() {
+    let mut interner = Interner::new();
+    let mut a = interner.intern("hello");
+    let _b = interner.intern("hello");
+    assert!(interner.get_mut(&mut a).is_none());
+}
+
+#[test]
+fn test_intern_get_mut_retires() {
+    let mut interner = Interner::new();
+    let mut a = interner.intern("hello");
+
+    interner.get_mut(&mut a).unwrap().make_ascii_uppercase();
+
+    assert_eq!(&*a, "HELLO");
+    assert!(interner.get("hello").is_none());
+    assert!(interner.get("HELLO").is_none());
+    assert!(interner.is_empty());
+    assert!(!Arc::ptr_eq(&interner.intern("hello"), &a));
+}
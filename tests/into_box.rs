@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::{ArcGetMutExt, into_box_drop_weak};
+
+#[test]
+fn test_into_box_weak_shared_success() {
+    let arc = Arc::new(vec![1, 2]);
+    let weak = Arc::downgrade(&arc);
+
+    let boxed = into_box_drop_weak(arc).unwrap();
+
+    assert_eq!(*boxed, vec![1, 2]);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_into_box_strong_shared_returns_arc() {
+    let arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+
+    let arc = arc.into_box_drop_weak().unwrap_err();
+    assert!(Arc::ptr_eq(&arc, &other));
+}
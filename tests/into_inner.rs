@@ -0,0 +1,26 @@
+use std::{rc::Rc, sync::Arc};
+
+use get_mut_drop_weak::into_inner_all_drop_weak;
+
+#[test]
+fn test_into_inner_all_splits_shared() {
+    let arcs = vec![Arc::new(0), Arc::new(1), Arc::new(2), Arc::new(3)];
+    let weak = Arc::downgrade(&arcs[1]);
+    let other = Arc::clone(&arcs[2]);
+
+    let (values, shared) = into_inner_all_drop_weak(arcs);
+    assert_eq!(values, [0, 1, 3]);
+    assert_eq!(shared.len(), 1);
+    assert!(Arc::ptr_eq(&shared[0], &other));
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_into_inner_all_rc() {
+    let rcs = vec![Rc::new(String::from("a")), Rc::new(String::from("b"))];
+    let _weak = Rc::downgrade(&rcs[0]);
+
+    let (values, shared) = into_inner_all_drop_weak(rcs);
+    assert_eq!(values, ["a", "b"]);
+    assert!(shared.is_empty());
+}
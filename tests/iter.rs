@@ -0,0 +1,52 @@
+use std::{collections::HashMap, sync::Arc};
+
+use get_mut_drop_weak::{Access, DropWeakMutExt};
+
+#[test]
+fn test_iter_yields_access_per_item() {
+    let mut arcs = [Arc::new(0), Arc::new(1), Arc::new(2)];
+    let weak = Arc::downgrade(&arcs[1]);
+    let _other = Arc::clone(&arcs[2]);
+
+    let outcomes: Vec<_> = arcs
+        .iter_mut()
+        .drop_weak_mut()
+        .map(|access| match access {
+            Access::Unchanged(_) => "unchanged",
+            Access::Replaced(_) => "replaced",
+            Access::Shared(_) => "shared",
+        })
+        .collect();
+
+    assert_eq!(outcomes, ["unchanged", "replaced", "shared"]);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_iter_filter_map_over_map_values() {
+    let mut map = HashMap::new();
+    map.insert(1, Arc::new(String::from("a")));
+    map.insert(2, Arc::new(String::from("b")));
+    let _other = Arc::clone(&map[&2]);
+
+    let edited = map
+        .values_mut()
+        .drop_weak_mut()
+        .filter_map(|access| access.into_result().ok())
+        .map(|value| value.push('!'))
+        .count();
+
+    assert_eq!(edited, 1);
+    assert_eq!(*map[&1], "a!");
+    assert_eq!(*map[&2], "b");
+}
+
+#[test]
+fn test_iter_rev_and_len() {
+    let mut arcs = [Arc::new(0), Arc::new(1)];
+    let mut iter = arcs.iter_mut().drop_weak_mut();
+    assert_eq!(iter.len(), 2);
+    *iter.next_back().unwrap().into_result().unwrap() = 5;
+    assert_eq!(iter.len(), 1);
+    assert_eq!(*arcs[1], 5);
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "std")]
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex, RwLock},
+};
+
+use get_mut_drop_weak::{OwnOrLock, OwnOrWrite, lock_or_own, write_or_own};
+
+#[test]
+fn test_lock_or_own_unique_skips_lock() {
+    let mut arc = Arc::new(Mutex::new(1));
+    let ptr = Arc::as_ptr(&arc);
+
+    let mut value = lock_or_own(&mut arc).unwrap();
+    assert!(matches!(value, OwnOrLock::Own(_)));
+    *value += 1;
+    drop(value);
+
+    assert_eq!(*arc.lock().unwrap(), 2);
+    assert_eq!(Arc::as_ptr(&arc), ptr);
+}
+
+#[test]
+fn test_lock_or_own_weak_shared_drops_weak() {
+    let mut arc = Arc::new(Mutex::new(1));
+    let weak = Arc::downgrade(&arc);
+
+    let mut value = lock_or_own(&mut arc).unwrap();
+    assert!(matches!(value, OwnOrLock::Own(_)));
+    *value += 1;
+    drop(value);
+
+    assert!(weak.upgrade().is_none());
+    assert_eq!(*arc.lock().unwrap(), 2);
+}
+
+#[test]
+fn test_lock_or_own_strong_shared_locks() {
+    let mut arc = Arc::new(Mutex::new(1));
+    let other = Arc::clone(&arc);
+
+    let mut value = lock_or_own(&mut arc).unwrap();
+    assert!(matches!(value, OwnOrLock::Locked(_)));
+    *value += 1;
+    assert!(other.try_lock().is_err());
+    drop(value);
+
+    assert_eq!(*other.lock().unwrap(), 2);
+}
+
+#[test]
+fn test_lock_or_own_poisoned() {
+    let mut arc = Arc::new(Mutex::new(1));
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _guard = arc.lock().unwrap();
+        panic!("poison");
+    }));
+
+    let err = lock_or_own(&mut arc).unwrap_err();
+    assert_eq!(*err.into_inner(), 1);
+
+    let other = Arc::clone(&arc);
+    let err = lock_or_own(&mut arc).unwrap_err();
+    assert!(matches!(err.into_inner(), OwnOrLock::Locked(_)));
+    drop(other);
+}
+
+#[test]
+fn test_write_or_own_weak_shared_drops_weak() {
+    let mut arc = Arc::new(RwLock::new(1));
+    let weak = Arc::downgrade(&arc);
+
+    let mut value = write_or_own(&mut arc).unwrap();
+    assert!(matches!(value, OwnOrWrite::Own(_)));
+    *value += 1;
+    drop(value);
+
+    assert!(weak.upgrade().is_none());
+    assert_eq!(*arc.read().unwrap(), 2);
+}
+
+#[test]
+fn test_write_or_own_strong_shared_locks() {
+    let mut arc = Arc::new(RwLock::new(1));
+    let other = Arc::clone(&arc);
+
+    let mut value = write_or_own(&mut arc).unwrap();
+    assert!(matches!(value, OwnOrWrite::Locked(_)));
+    *value += 1;
+    assert!(other.try_read().is_err());
+    drop(value);
+
+    assert_eq!(*other.read().unwrap(), 2);
+}
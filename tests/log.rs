@@ -0,0 +1,54 @@
+#![cfg(feature = "log")]
+
+use std::sync::{Arc, Mutex};
+
+use get_mut_drop_weak::get_mut_drop_weak;
+use log::{Level, Log, Metadata, Record};
+
+/// Records the level and message of every log record.
+struct Recorder {
+    records: Mutex<Vec<(Level, String)>>,
+}
+
+impl Log for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        self.records
+            .lock()
+            .unwrap()
+            .push((record.level(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+static RECORDER: Recorder = Recorder {
+    records: Mutex::new(Vec::new()),
+};
+
+#[test]
+fn test_log_records() {
+    log::set_logger(&RECORDER).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let mut arc = Arc::new(1u8);
+    let _weak = Arc::downgrade(&arc);
+    get_mut_drop_weak(&mut arc).unwrap();
+
+    // Unique and strongly shared pointers aren't logged.
+    get_mut_drop_weak(&mut arc).unwrap();
+    let _other = Arc::clone(&arc);
+    get_mut_drop_weak(&mut arc).unwrap_err();
+
+    let records = RECORDER.records.lock().unwrap();
+    assert_eq!(
+        *records,
+        [(
+            Level::Debug,
+            String::from("replaced the allocation of a u8, orphaning 1 weak pointers")
+        )]
+    );
+}
@@ -0,0 +1,24 @@
+#![cfg(loom)]
+
+use get_mut_drop_weak::get_mut_drop_weak;
+use loom::sync::Arc;
+use loom::thread;
+
+#[test]
+fn loom_strong_shared_then_exclusive() {
+    loom::model(|| {
+        let mut arc = Arc::new(0usize);
+        let arc2 = Arc::clone(&arc);
+
+        let handle = thread::spawn(move || drop(arc2));
+
+        // Depending on the interleaving the other strong reference may or may not be gone yet.
+        if let Ok(value) = get_mut_drop_weak(&mut arc) {
+            *value += 1;
+        }
+        handle.join().unwrap();
+
+        *get_mut_drop_weak(&mut arc).unwrap() += 1;
+        assert!(*arc == 1 || *arc == 2);
+    });
+}
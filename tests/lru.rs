@@ -0,0 +1,75 @@
+#![cfg(feature = "lru")]
+
+use std::sync::Arc;
+
+use get_mut_drop_weak::lru::LruCache;
+
+#[test]
+fn test_lru_evicts_least_recently_used() {
+    let mut cache = LruCache::new(2);
+    assert_eq!(cache.insert("a", 1), None);
+    assert_eq!(cache.insert("b", 2), None);
+    cache.get(&"a");
+    assert_eq!(cache.insert("c", 3), Some("b"));
+    assert!(cache.contains_key(&"a") && cache.contains_key(&"c"));
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn test_lru_get_mut_orphans_observers() {
+    let mut cache = LruCache::new(2);
+    cache.insert("a", 1);
+    let observer = cache.observe(&"a").unwrap();
+
+    *cache.get_mut(&"a").unwrap() += 1;
+
+    assert!(observer.upgrade().is_none());
+    assert_eq!(*cache.get(&"a").unwrap(), 2);
+}
+
+#[test]
+fn test_lru_get_mut_strongly_held() {
+    let mut cache = LruCache::new(2);
+    cache.insert("a", 1);
+    let held = cache.get(&"a").unwrap();
+    assert!(cache.get_mut(&"a").is_none());
+    drop(held);
+    assert!(cache.get_mut(&"a").is_some());
+}
+
+#[test]
+fn test_lru_recycles_evicted_allocation() {
+    let mut cache = LruCache::new(1);
+    cache.insert("a", 1);
+    let observer = cache.observe(&"a").unwrap();
+    let address = Arc::as_ptr(&cache.get(&"a").unwrap());
+
+    assert_eq!(cache.insert("b", 2), Some("a"));
+
+    assert!(observer.upgrade().is_none());
+    let b = cache.get(&"b").unwrap();
+    assert_eq!(*b, 2);
+    // The observer forced get_mut_drop_weak to move the recycled slot.
+    assert_ne!(Arc::as_ptr(&b), address);
+}
+
+#[test]
+fn test_lru_evicted_strongly_held_keeps_value() {
+    let mut cache = LruCache::new(1);
+    cache.insert("a", 1);
+    let held = cache.get(&"a").unwrap();
+    cache.insert("b", 2);
+    assert_eq!(*held, 1);
+    assert_eq!(*cache.get(&"b").unwrap(), 2);
+}
+
+#[test]
+fn test_lru_overwrite_in_place() {
+    let mut cache = LruCache::new(1);
+    cache.insert("a", 1);
+    let address = Arc::as_ptr(&cache.get(&"a").unwrap());
+    assert_eq!(cache.insert("a", 2), None);
+    assert_eq!(Arc::as_ptr(&cache.get(&"a").unwrap()), address);
+    assert_eq!(cache.remove(&"a").as_deref(), Some(&2));
+    assert!(cache.is_empty());
+}
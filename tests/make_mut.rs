@@ -0,0 +1,41 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use get_mut_drop_weak::{ArcGetMutExt, make_mut_drop_weak};
+
+#[derive(Debug, PartialEq)]
+struct CountedClone(u32, std::cell::Cell<u32>);
+
+impl Clone for CountedClone {
+    fn clone(&self) -> Self {
+        self.1.set(self.1.get() + 1);
+        CountedClone(self.0, std::cell::Cell::new(0))
+    }
+}
+
+#[test]
+fn test_make_mut_weak_only_moves_instead_of_cloning() {
+    let mut rc = Rc::new(CountedClone(1, Default::default()));
+    let weak = Rc::downgrade(&rc);
+
+    make_mut_drop_weak(&mut rc).0 = 2;
+
+    assert_eq!(rc.0, 2);
+    assert_eq!(rc.1.get(), 0); // never cloned
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_make_mut_strong_shared_clones() {
+    let mut arc1 = Arc::new(vec![1]);
+    let arc2 = Arc::clone(&arc1);
+    let weak = Arc::downgrade(&arc1);
+
+    arc1.make_mut_drop_weak().push(2);
+
+    assert_eq!(*arc1, vec![1, 2]);
+    assert_eq!(*arc2, vec![1]);
+    assert!(!Arc::ptr_eq(&arc1, &arc2));
+    // The weak pointer still refers to the allocation `arc2` keeps alive.
+    assert!(Arc::ptr_eq(&weak.upgrade().unwrap(), &arc2));
+}
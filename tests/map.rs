@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::get_mut_drop_weak_map;
+
+struct Config {
+    name: String,
+    limits: Limits,
+}
+
+struct Limits {
+    max: u32,
+}
+
+fn max_mut(config: &mut Arc<Config>) -> Option<&mut u32> {
+    // Returning the projected reference straight out of the function.
+    get_mut_drop_weak_map(config, |c| &mut c.limits.max).ok()
+}
+
+#[test]
+fn test_map_weak_shared_projects_field() {
+    let mut config = Arc::new(Config {
+        name: String::from("a"),
+        limits: Limits { max: 1 },
+    });
+    let weak = Arc::downgrade(&config);
+
+    *max_mut(&mut config).unwrap() = 5;
+    assert_eq!(config.limits.max, 5);
+    assert_eq!(config.name, "a");
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_map_strong_shared_returns_pointer() {
+    let mut config = Arc::new(Config {
+        name: String::from("a"),
+        limits: Limits { max: 1 },
+    });
+    let other = Arc::clone(&config);
+
+    let err = get_mut_drop_weak_map(&mut config, |c| c.name.as_mut_str()).unwrap_err();
+    assert!(Arc::ptr_eq(err, &other));
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "metrics")]
+
+use std::sync::{Arc, Mutex};
+
+use get_mut_drop_weak::get_mut_drop_weak;
+use metrics::{
+    Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+
+/// Records the name and value of every counter increment and histogram
+/// sample.
+#[derive(Default)]
+struct Log(Mutex<Vec<(String, f64)>>);
+
+struct Handle {
+    name: String,
+    log: Arc<Log>,
+}
+
+impl CounterFn for Handle {
+    fn increment(&self, value: u64) {
+        let mut log = self.log.0.lock().unwrap();
+        log.push((self.name.clone(), value as f64));
+    }
+
+    fn absolute(&self, _value: u64) {
+        unimplemented!()
+    }
+}
+
+impl HistogramFn for Handle {
+    fn record(&self, value: f64) {
+        let mut log = self.log.0.lock().unwrap();
+        log.push((self.name.clone(), value));
+    }
+}
+
+#[derive(Default)]
+struct TestRecorder(Arc<Log>);
+
+impl TestRecorder {
+    fn handle(&self, key: &Key) -> Arc<Handle> {
+        Arc::new(Handle {
+            name: key.name().to_owned(),
+            log: Arc::clone(&self.0),
+        })
+    }
+}
+
+impl Recorder for TestRecorder {
+    fn describe_counter(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_gauge(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_histogram(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+        Counter::from_arc(self.handle(key))
+    }
+
+    fn register_gauge(&self, _key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+        Histogram::from_arc(self.handle(key))
+    }
+}
+
+#[test]
+fn test_metrics() {
+    let recorder = TestRecorder::default();
+    metrics::with_local_recorder(&recorder, || {
+        let mut arc = Arc::new(1u8);
+        let _weak1 = Arc::downgrade(&arc);
+        let _weak2 = Arc::downgrade(&arc);
+        get_mut_drop_weak(&mut arc).unwrap();
+        get_mut_drop_weak(&mut arc).unwrap();
+        let _other = Arc::clone(&arc);
+        get_mut_drop_weak(&mut arc).unwrap_err();
+    });
+
+    let log = recorder.0.0.lock().unwrap();
+    let names: Vec<_> = log
+        .iter()
+        .map(|(name, value)| (name.as_str(), *value))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            ("get_mut_drop_weak.replacements", 1.0),
+            ("get_mut_drop_weak.orphaned_weaks", 2.0),
+            ("get_mut_drop_weak.fast_path", 1.0),
+            ("get_mut_drop_weak.strongly_shared", 1.0),
+        ]
+    );
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::{ArcGetMutExt, ArcMutGuard, get_mut_drop_weak_guard};
+
+#[derive(Debug)]
+struct Doc {
+    title: String,
+    tags: Vec<String>,
+}
+
+// Exclusive access stored in a struct field.
+struct TitleEditor<'a> {
+    title: ArcMutGuard<'a, String>,
+}
+
+impl TitleEditor<'_> {
+    fn append(&mut self, s: &str) {
+        self.title.push_str(s);
+    }
+}
+
+#[test]
+fn test_guard_map_weak_shared() {
+    let mut doc = Arc::new(Doc {
+        title: String::from("draft"),
+        tags: vec![],
+    });
+    let weak = Arc::downgrade(&doc);
+
+    let guard = get_mut_drop_weak_guard(&mut doc).unwrap();
+    let mut editor = TitleEditor {
+        title: ArcMutGuard::map(guard, |d| &mut d.title),
+    };
+    editor.append(" v2");
+
+    assert_eq!(doc.title, "draft v2");
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_guard_try_map() {
+    let mut doc = Arc::new(Doc {
+        title: String::new(),
+        tags: vec![String::from("a")],
+    });
+
+    let guard = doc.get_mut_drop_weak_guard().unwrap();
+    let guard = ArcMutGuard::try_map(guard, |d| d.tags.get_mut(1)).unwrap_err();
+    let mut tag = ArcMutGuard::try_map(guard, |d| d.tags.get_mut(0)).unwrap();
+    tag.push('b');
+    assert_eq!(*tag, "ab");
+}
+
+#[test]
+fn test_guard_strong_shared() {
+    let mut doc = Arc::new(Doc {
+        title: String::new(),
+        tags: vec![],
+    });
+    let other = Arc::clone(&doc);
+
+    let err = get_mut_drop_weak_guard(&mut doc).err().unwrap();
+    assert!(Arc::ptr_eq(err, &other));
+}
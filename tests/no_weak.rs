@@ -0,0 +1,52 @@
+use std::sync::Arc;
+use std::thread;
+
+use get_mut_drop_weak::NoWeakArc;
+
+#[test]
+fn test_no_weak_get_mut() {
+    let mut arc = NoWeakArc::new(1);
+    *NoWeakArc::get_mut(&mut arc).unwrap() += 1;
+    assert_eq!(*arc, 2);
+
+    let other = arc.clone();
+    assert!(NoWeakArc::get_mut(&mut arc).is_none());
+    assert_eq!(NoWeakArc::strong_count(&arc), 2);
+    assert!(NoWeakArc::ptr_eq(&arc, &other));
+
+    thread::spawn(move || drop(other)).join().unwrap();
+    assert!(NoWeakArc::get_mut(&mut arc).is_some());
+}
+
+#[test]
+fn test_no_weak_from_arc() {
+    let arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+    let arc = NoWeakArc::from_arc(arc).unwrap_err();
+    drop(weak);
+
+    let other = Arc::clone(&arc);
+    let arc = NoWeakArc::from_arc(arc).unwrap_err();
+    drop(other);
+
+    let arc = NoWeakArc::from_arc(arc).unwrap();
+    let arc: Arc<i32> = arc.try_into().unwrap();
+    assert_eq!(Arc::weak_count(&arc), 0);
+}
+
+#[test]
+fn test_no_weak_into_arc_shared() {
+    let mut arc = NoWeakArc::new(1);
+    let other = arc.clone();
+
+    // A plain `Arc` could be downgraded, and the weak pointer upgraded while
+    // `get_mut` hands out a reference.
+    let other = NoWeakArc::into_arc(other).unwrap_err();
+    assert!(Arc::<i32>::try_from(other.clone()).is_err());
+    drop(other);
+
+    *NoWeakArc::get_mut(&mut arc).unwrap() += 1;
+    let arc = NoWeakArc::into_arc(arc).unwrap();
+    let weak = Arc::downgrade(&arc);
+    assert_eq!(*weak.upgrade().unwrap(), 2);
+}
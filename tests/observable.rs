@@ -0,0 +1,49 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+use get_mut_drop_weak::ObservableArc;
+
+#[test]
+fn test_observable_notifies_on_replacement() {
+    let mut observable = ObservableArc::new(1);
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    observable.subscribe(move || {
+        counter.fetch_add(1, Ordering::Relaxed);
+    });
+    let (sender, receiver) = mpsc::channel();
+    observable.subscribe(move || sender.send(()).unwrap());
+
+    let weak = ObservableArc::downgrade(&observable);
+    *observable.get_mut_drop_weak().unwrap() += 1;
+
+    assert_eq!(*observable, 2);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+    receiver.try_recv().unwrap();
+}
+
+#[test]
+fn test_observable_silent_without_weak() {
+    let mut observable = ObservableArc::from(Arc::new(1));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    observable.subscribe(move || {
+        counter.fetch_add(1, Ordering::Relaxed);
+    });
+
+    *observable.get_mut_drop_weak().unwrap() += 1;
+
+    assert_eq!(calls.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn test_observable_strong_shared() {
+    let mut observable = ObservableArc::new(1);
+    let other = Arc::clone(ObservableArc::as_arc(&observable));
+    observable.subscribe(|| panic!("not replaced"));
+
+    assert!(observable.get_mut_drop_weak().is_none());
+    assert!(Arc::ptr_eq(&ObservableArc::into_arc(observable), &other));
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+#[cfg(feature = "std")]
+use std::{thread, time::Duration};
+
+use get_mut_drop_weak::{GetMutOptions, get_mut_with};
+
+#[test]
+fn test_options_default_replaces() {
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+    *get_mut_with(&mut arc, &GetMutOptions::default()).unwrap() += 1;
+    assert!(weak.upgrade().is_none());
+
+    let _other = Arc::clone(&arc);
+    assert!(get_mut_with(&mut arc, &GetMutOptions::default()).is_err());
+}
+
+#[test]
+fn test_options_no_replace_keeps_weak() {
+    let options = GetMutOptions::new().allow_replace(false);
+
+    let mut arc = Arc::new(1);
+    *get_mut_with(&mut arc, &options).unwrap() += 1;
+
+    let weak = Arc::downgrade(&arc);
+    assert!(get_mut_with(&mut arc, &options).is_err());
+    assert_eq!(weak.upgrade().as_deref(), Some(&2));
+}
+
+#[test]
+fn test_options_no_replace_clone() {
+    let options = GetMutOptions::new().allow_replace(false).allow_clone();
+
+    let mut arc = Arc::new(String::from("a"));
+    let weak = Arc::downgrade(&arc);
+    let other = Arc::clone(&arc);
+    get_mut_with(&mut arc, &options).unwrap().push('b');
+
+    assert_eq!(*arc, "ab");
+    assert_eq!(*other, "a");
+    assert!(Arc::ptr_eq(&weak.upgrade().unwrap(), &other));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_options_wait() {
+    let options = GetMutOptions::new().wait(Duration::from_secs(10));
+
+    let mut arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+    let reader = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        drop(other);
+    });
+
+    *get_mut_with(&mut arc, &options).unwrap() += 1;
+    assert_eq!(*arc, 2);
+    reader.join().unwrap();
+}
+
+#[test]
+fn test_options_debug() {
+    let options = GetMutOptions::<u8>::new().max_retries(2);
+    assert!(format!("{options:?}").contains("max_retries: 2"));
+}
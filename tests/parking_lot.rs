@@ -0,0 +1,73 @@
+#![cfg(feature = "parking_lot")]
+
+use std::sync::Arc;
+
+use get_mut_drop_weak::parking_lot::{OwnOrLock, OwnOrWrite, lock_or_own, write_or_own};
+use parking_lot::{Mutex, RwLock};
+
+#[test]
+fn test_parking_lot_lock_or_own_unique_skips_lock() {
+    let mut arc = Arc::new(Mutex::new(1));
+    let ptr = Arc::as_ptr(&arc);
+
+    let mut value = lock_or_own(&mut arc);
+    assert!(matches!(value, OwnOrLock::Own(_)));
+    *value += 1;
+    drop(value);
+
+    assert_eq!(*arc.lock(), 2);
+    assert_eq!(Arc::as_ptr(&arc), ptr);
+}
+
+#[test]
+fn test_parking_lot_lock_or_own_weak_shared_drops_weak() {
+    let mut arc = Arc::new(Mutex::new(1));
+    let weak = Arc::downgrade(&arc);
+
+    *lock_or_own(&mut arc) += 1;
+
+    assert!(weak.upgrade().is_none());
+    assert_eq!(*arc.lock(), 2);
+}
+
+#[test]
+fn test_parking_lot_lock_or_own_strong_shared_locks() {
+    let mut arc = Arc::new(Mutex::new(1));
+    let other = Arc::clone(&arc);
+
+    let mut value = lock_or_own(&mut arc);
+    assert!(matches!(value, OwnOrLock::Locked(_)));
+    *value += 1;
+    assert!(other.try_lock().is_none());
+    drop(value);
+
+    assert_eq!(*other.lock(), 2);
+}
+
+#[test]
+fn test_parking_lot_write_or_own_weak_shared_drops_weak() {
+    let mut arc = Arc::new(RwLock::new(1));
+    let weak = Arc::downgrade(&arc);
+
+    let mut value = write_or_own(&mut arc);
+    assert!(matches!(value, OwnOrWrite::Own(_)));
+    *value += 1;
+    drop(value);
+
+    assert!(weak.upgrade().is_none());
+    assert_eq!(*arc.read(), 2);
+}
+
+#[test]
+fn test_parking_lot_write_or_own_strong_shared_locks() {
+    let mut arc = Arc::new(RwLock::new(1));
+    let other = Arc::clone(&arc);
+
+    let mut value = write_or_own(&mut arc);
+    assert!(matches!(value, OwnOrWrite::Locked(_)));
+    *value += 1;
+    assert!(other.try_read().is_none());
+    drop(value);
+
+    assert_eq!(*other.read(), 2);
+}
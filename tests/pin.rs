@@ -0,0 +1,55 @@
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use get_mut_drop_weak::{get_mut_drop_weak_pinned, get_mut_drop_weak_pinned_unchecked};
+
+#[test]
+fn test_pinned_unpin_drops_weak() {
+    let mut pinned = Arc::pin(String::from("hello"));
+    // There is no safe way to downgrade a pinned Arc, so go through the inner Arc.
+    let inner = unsafe { Pin::into_inner_unchecked(pinned) };
+    let weak = Arc::downgrade(&inner);
+    pinned = unsafe { Pin::new_unchecked(inner) };
+
+    get_mut_drop_weak_pinned(&mut pinned)
+        .unwrap()
+        .get_mut()
+        .push_str(" world");
+
+    assert_eq!(*pinned, "hello world");
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_pinned_strong_shared_no_mut() {
+    let mut pinned = Arc::pin(1);
+    let other = Pin::clone(&pinned);
+
+    get_mut_drop_weak_pinned(&mut pinned).unwrap_err();
+    drop(other);
+    *get_mut_drop_weak_pinned(&mut pinned).unwrap() += 1;
+    assert_eq!(*pinned, 2);
+}
+
+#[test]
+fn test_pinned_unchecked_not_unpin() {
+    #[derive(Debug)]
+    struct NotUnpin {
+        value: i32,
+        _pin: PhantomPinned,
+    }
+
+    let inner = Arc::new(NotUnpin {
+        value: 1,
+        _pin: PhantomPinned,
+    });
+    let weak = Arc::downgrade(&inner);
+    let mut pinned = unsafe { Pin::new_unchecked(inner) };
+
+    // SAFETY: `NotUnpin` holds no self-references, so moving it is harmless.
+    let value = unsafe { get_mut_drop_weak_pinned_unchecked(&mut pinned) }.unwrap();
+    unsafe { value.get_unchecked_mut() }.value += 1;
+    assert_eq!(pinned.value, 2);
+    assert!(weak.upgrade().is_none());
+}
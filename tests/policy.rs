@@ -0,0 +1,98 @@
+#![cfg(feature = "std")]
+
+use std::{sync::Arc, thread, time::Duration};
+
+use get_mut_drop_weak::{
+    CloneBelow, Exclusivity, Reclaim, ReclaimPolicy, ReplaceOrClone, ReplaceOrWait, ReplaceWeak,
+    get_mut_with_policy,
+};
+
+struct Never;
+
+impl<T> ReclaimPolicy<T> for Never {
+    fn decide(&self, _value: &T, _state: Exclusivity) -> Reclaim<T> {
+        Reclaim::Fail
+    }
+}
+
+#[test]
+fn test_policy_unique_ignores_policy() {
+    let mut arc = Arc::new(1);
+    *get_mut_with_policy(&mut arc, &Never).unwrap() += 1;
+    assert_eq!(*arc, 2);
+}
+
+#[test]
+fn test_policy_replace_weak() {
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+    *get_mut_with_policy(&mut arc, &ReplaceWeak).unwrap() += 1;
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_policy_replace_weak_strongly_shared() {
+    let mut arc = Arc::new(1);
+    let _other = Arc::clone(&arc);
+    assert!(get_mut_with_policy(&mut arc, &ReplaceWeak).is_err());
+}
+
+#[test]
+fn test_policy_fail_keeps_weak() {
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+    assert!(get_mut_with_policy(&mut arc, &Never).is_err());
+    assert!(weak.upgrade().is_some());
+}
+
+#[test]
+fn test_policy_clone() {
+    let mut arc = Arc::new(vec![1]);
+    let other = Arc::clone(&arc);
+    get_mut_with_policy(&mut arc, &ReplaceOrClone)
+        .unwrap()
+        .push(2);
+    assert_eq!(*arc, [1, 2]);
+    assert_eq!(*other, [1]);
+}
+
+#[test]
+fn test_policy_wait() {
+    let mut arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+    let reader = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        drop(other);
+    });
+
+    *get_mut_with_policy(&mut arc, &ReplaceOrWait(Duration::from_secs(10))).unwrap() += 1;
+    assert_eq!(*arc, 2);
+    reader.join().unwrap();
+}
+
+#[test]
+fn test_policy_clone_below_small() {
+    let mut arc = Arc::new(1u8);
+    let weak = Arc::downgrade(&arc);
+    *get_mut_with_policy(&mut arc, &CloneBelow(16)).unwrap() += 1;
+
+    assert_eq!(*arc, 2);
+    // The original was cloned and dropped, which orphans the weak pointer too.
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_policy_clone_below_large_replaces() {
+    #[derive(Debug, Clone)]
+    struct Large([u8; 64]);
+
+    let mut arc = Arc::new(Large([0; 64]));
+    let weak = Arc::downgrade(&arc);
+    get_mut_with_policy(&mut arc, &CloneBelow(16)).unwrap().0[0] = 1;
+    assert_eq!(arc.0[0], 1);
+    assert!(weak.upgrade().is_none());
+
+    let other = Arc::clone(&arc);
+    get_mut_with_policy(&mut arc, &CloneBelow(16)).unwrap().0[0] = 2;
+    assert_eq!((arc.0[0], other.0[0]), (2, 1));
+}
@@ -0,0 +1,69 @@
+use std::{sync::Arc, thread};
+
+use get_mut_drop_weak::{ArcPool, get_mut_drop_weak_pooled};
+
+#[test]
+fn test_pooled_weakly_shared() {
+    let pool = ArcPool::new(4);
+    let mut arc = Arc::new(String::from("a"));
+    let weak = Arc::downgrade(&arc);
+
+    get_mut_drop_weak_pooled(&mut arc, &pool).unwrap().push('b');
+
+    assert_eq!(*arc, "ab");
+    assert!(weak.upgrade().is_none());
+    // One batch was allocated and one node used.
+    assert_eq!(pool.len(), 3);
+}
+
+#[test]
+fn test_pooled_unique_takes_nothing() {
+    let pool = ArcPool::<i32>::new(4);
+    let mut arc = Arc::new(1);
+    *get_mut_drop_weak_pooled(&mut arc, &pool).unwrap() += 1;
+    assert_eq!(*arc, 2);
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn test_pooled_strongly_shared() {
+    let pool = ArcPool::new(4);
+    let mut arc = Arc::new(1);
+    let _other = Arc::clone(&arc);
+    assert!(get_mut_drop_weak_pooled(&mut arc, &pool).is_err());
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn test_pooled_reserve() {
+    let pool = ArcPool::new(1);
+    pool.reserve(3);
+    let mut arcs: Vec<_> = (0..3).map(Arc::new).collect();
+    let weaks: Vec<_> = arcs.iter().map(Arc::downgrade).collect();
+    for arc in &mut arcs {
+        *get_mut_drop_weak_pooled(arc, &pool).unwrap() += 10;
+    }
+    assert!(pool.is_empty());
+    assert!(weaks.iter().all(|weak| weak.upgrade().is_none()));
+    assert_eq!(
+        arcs.iter().map(|arc| **arc).collect::<Vec<_>>(),
+        [10, 11, 12]
+    );
+}
+
+#[test]
+fn test_pooled_lost_race_keeps_node() {
+    let pool = ArcPool::new(1);
+    for _ in 0..100 {
+        let mut arc = Arc::new(0);
+        let weak = Arc::downgrade(&arc);
+        let upgrader = thread::spawn(move || drop(weak.upgrade()));
+        let before = pool.len();
+        if get_mut_drop_weak_pooled(&mut arc, &pool).is_err() {
+            // Either refused before taking a node, or the node went back.
+            assert!(pool.len() >= before);
+        }
+        upgrader.join().unwrap();
+    }
+    assert!(pool.len() <= 1);
+}
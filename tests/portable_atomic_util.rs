@@ -0,0 +1,28 @@
+#![cfg(feature = "portable-atomic-util")]
+
+use get_mut_drop_weak::get_mut_drop_weak;
+use portable_atomic_util::Arc;
+
+#[test]
+fn test_portable_arc_weak_shared_drops_weak_success() {
+    let mut arc = Arc::new(String::from("hello"));
+    let weak = Arc::downgrade(&arc);
+    let original_ptr = Arc::as_ptr(&arc);
+
+    get_mut_drop_weak(&mut arc).unwrap().push_str(" world");
+
+    assert_eq!(*arc, "hello world");
+    assert_eq!(Arc::weak_count(&arc), 0);
+    assert_ne!(Arc::as_ptr(&arc), original_ptr);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_portable_arc_strong_shared_no_mut() {
+    let mut arc1 = Arc::new(5);
+    let arc2 = Arc::clone(&arc1);
+
+    get_mut_drop_weak(&mut arc1).unwrap_err();
+    assert_eq!(Arc::strong_count(&arc1), 2);
+    drop(arc2);
+}
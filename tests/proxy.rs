@@ -0,0 +1,47 @@
+#![cfg(feature = "std")]
+
+use std::sync::Arc;
+
+use get_mut_drop_weak::ProxyArc;
+
+#[test]
+fn test_proxy_survives_replacement() {
+    let mut arc = ProxyArc::new(1);
+    let proxy = ProxyArc::proxy(&arc);
+    let weak = ProxyArc::downgrade(&arc);
+
+    {
+        let mut value = arc.get_mut_drop_weak().unwrap();
+        *value += 1;
+        assert!(proxy.upgrade().is_none());
+    }
+
+    assert!(weak.upgrade().is_none());
+    assert_eq!(*proxy.upgrade().unwrap(), 2);
+    assert_eq!(*arc, 2);
+}
+
+#[test]
+fn test_proxy_only_keeps_allocation() {
+    let mut arc = ProxyArc::new(1);
+    let proxy = ProxyArc::proxy(&arc);
+    let original_ptr: *const i32 = &*arc;
+
+    *arc.get_mut_drop_weak().unwrap() += 1;
+
+    assert_eq!(&*arc as *const i32, original_ptr);
+    assert!(Arc::ptr_eq(
+        &proxy.upgrade().unwrap(),
+        &proxy.clone().upgrade().unwrap()
+    ));
+}
+
+#[test]
+fn test_proxy_strong_shared_reconnects() {
+    let mut arc = ProxyArc::new(1);
+    let other = arc.clone();
+    let proxy = ProxyArc::proxy(&other);
+
+    assert!(arc.get_mut_drop_weak().is_none());
+    assert_eq!(*proxy.upgrade().unwrap(), 1);
+}
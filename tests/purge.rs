@@ -0,0 +1,53 @@
+#![cfg(feature = "std")]
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    rc::Rc,
+    sync::{Arc, Weak},
+};
+
+use get_mut_drop_weak::{PurgeDeadWeaks, get_mut_drop_weak};
+
+#[test]
+fn test_purge_vec() {
+    let mut live = Arc::new(1);
+    let mut orphaned = Arc::new(2);
+    let mut weaks = vec![
+        Arc::downgrade(&live),
+        Arc::downgrade(&orphaned),
+        Weak::new(),
+    ];
+
+    get_mut_drop_weak(&mut orphaned).unwrap();
+
+    assert_eq!(weaks.purge_dead_weaks(), 2);
+    assert_eq!(weaks.len(), 1);
+    assert!(weaks[0].upgrade().is_some());
+    assert_eq!(weaks.purge_dead_weaks(), 0);
+    // Still alive; only the other one was orphaned.
+    assert!(Arc::get_mut(&mut live).is_none());
+}
+
+#[test]
+fn test_purge_maps() {
+    let live: Arc<str> = Arc::from("live");
+    let dead = Arc::downgrade(&Arc::<str>::from("dead"));
+
+    let mut hash = HashMap::from([(1, Arc::downgrade(&live)), (2, dead.clone())]);
+    let mut btree = BTreeMap::from([(1, Arc::downgrade(&live)), (2, dead.clone())]);
+    let mut deque = VecDeque::from([dead, Arc::downgrade(&live)]);
+
+    assert_eq!(hash.purge_dead_weaks(), 1);
+    assert_eq!(btree.purge_dead_weaks(), 1);
+    assert_eq!(deque.purge_dead_weaks(), 1);
+    assert!(hash.contains_key(&1) && btree.contains_key(&1));
+    assert_eq!(deque.len(), 1);
+}
+
+#[test]
+fn test_purge_rc() {
+    let mut rc = Rc::new(1);
+    let mut weaks = vec![Rc::downgrade(&rc)];
+    get_mut_drop_weak(&mut rc).unwrap();
+    assert_eq!(weaks.purge_dead_weaks(), 1);
+}
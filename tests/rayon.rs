@@ -0,0 +1,36 @@
+#![cfg(feature = "rayon")]
+
+use std::{collections::HashMap, sync::Arc};
+
+use get_mut_drop_weak::{BatchOutcome, par_get_mut_drop_weak_batch, par_make_unique_all_drop_weak};
+
+#[test]
+fn test_par_batch_matches_scalar() {
+    let mut arcs: Vec<_> = (0..10_000).map(Arc::new).collect();
+    let weaks: Vec<_> = arcs.iter().step_by(3).map(Arc::downgrade).collect();
+    let others: Vec<_> = arcs.iter().skip(1).step_by(3).map(Arc::clone).collect();
+
+    let outcomes = par_get_mut_drop_weak_batch(&mut arcs);
+
+    for (i, outcome) in outcomes.iter().enumerate() {
+        let expected = match i % 3 {
+            0 => BatchOutcome::Replaced,
+            1 => BatchOutcome::Shared,
+            _ => BatchOutcome::Exclusive,
+        };
+        assert_eq!(*outcome, expected, "index {i}");
+    }
+    assert!(weaks.iter().all(|weak| weak.upgrade().is_none()));
+    assert_eq!(others.len(), 3333);
+    assert!(arcs.iter().enumerate().all(|(i, arc)| **arc == i));
+}
+
+#[test]
+fn test_par_make_unique_all() {
+    let mut map: HashMap<_, _> = (0..1000).map(|i| (i, Arc::new(i))).collect();
+    let _weak = Arc::downgrade(&map[&1]);
+    let _other = Arc::clone(&map[&2]);
+
+    assert_eq!(par_make_unique_all_drop_weak(&mut map), [2]);
+    assert!(Arc::get_mut(map.get_mut(&1).unwrap()).is_some());
+}
@@ -0,0 +1,87 @@
+use std::cell::RefCell;
+use std::ptr;
+use std::rc::Rc;
+
+use get_mut_drop_weak::rc::{OwnOrBorrow, borrow_mut_or_own, get_mut_drop_weak};
+
+#[test]
+fn test_rc_exclusive_access_no_weak() {
+    // Scenario: Strong count = 1, Weak count = 0
+    let mut rc = Rc::new(10);
+    let original_ptr = Rc::as_ptr(&rc);
+
+    let val_mut = get_mut_drop_weak(&mut rc).unwrap();
+    *val_mut = 20;
+
+    assert_eq!(*rc, 20);
+    assert_eq!(Rc::weak_count(&rc), 0);
+    // Ensure the Rc instance itself wasn't replaced
+    assert_eq!(Rc::as_ptr(&rc), original_ptr);
+}
+
+#[test]
+fn test_rc_strong_shared_no_mut() {
+    // Scenario: Strong count > 1, Weak count > 0
+    let mut rc1 = Rc::new(String::from("hello"));
+    let rc2 = Rc::clone(&rc1);
+    let weak = Rc::downgrade(&rc1);
+    let original_ptr = Rc::as_ptr(&rc1);
+
+    let err_ref = get_mut_drop_weak(&mut rc1).unwrap_err();
+    assert!(ptr::eq(err_ref, &rc1));
+
+    // Check state hasn't changed
+    assert_eq!(Rc::strong_count(&rc1), 2);
+    assert_eq!(Rc::weak_count(&rc1), 1);
+    assert!(weak.upgrade().is_some());
+    assert_eq!(Rc::as_ptr(&rc1), original_ptr);
+    drop(rc2);
+}
+
+#[test]
+fn test_rc_weak_shared_drops_weak_success() {
+    // Scenario: Strong count = 1, Weak count > 0
+    let mut rc = Rc::new(vec![1, 2, 3]);
+    let weak1 = Rc::downgrade(&rc);
+    let weak2 = Rc::downgrade(&rc);
+    let original_ptr = Rc::as_ptr(&rc);
+
+    let val_mut = get_mut_drop_weak(&mut rc).unwrap();
+    val_mut.push(4);
+
+    assert_eq!(*rc, vec![1, 2, 3, 4]);
+    assert_eq!(Rc::strong_count(&rc), 1);
+    assert_eq!(Rc::weak_count(&rc), 0);
+    // Verify the Rc instance was replaced and the old weak pointers are dangling
+    assert_ne!(Rc::as_ptr(&rc), original_ptr);
+    assert!(weak1.upgrade().is_none());
+    assert!(weak2.upgrade().is_none());
+}
+
+#[test]
+fn test_rc_borrow_mut_or_own_weak_shared_drops_weak() {
+    let mut rc = Rc::new(RefCell::new(1));
+    let weak = Rc::downgrade(&rc);
+
+    let mut value = borrow_mut_or_own(&mut rc);
+    assert!(matches!(value, OwnOrBorrow::Own(_)));
+    *value += 1;
+    drop(value);
+
+    assert!(weak.upgrade().is_none());
+    assert_eq!(*rc.borrow(), 2);
+}
+
+#[test]
+fn test_rc_borrow_mut_or_own_strong_shared_borrows() {
+    let mut rc = Rc::new(RefCell::new(1));
+    let other = Rc::clone(&rc);
+
+    let mut value = borrow_mut_or_own(&mut rc);
+    assert!(matches!(value, OwnOrBorrow::Locked(_)));
+    *value += 1;
+    assert!(other.try_borrow().is_err());
+    drop(value);
+
+    assert_eq!(*other.borrow(), 2);
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "rclite")]
+
+use get_mut_drop_weak::get_mut_drop_weak;
+use rclite::{Arc, Rc};
+
+#[test]
+fn test_rclite_arc_exclusive_and_shared() {
+    let mut arc1 = Arc::new(1);
+    let arc2 = Arc::clone(&arc1);
+    get_mut_drop_weak(&mut arc1).unwrap_err();
+    drop(arc2);
+
+    // Without weak pointers the allocation is never replaced.
+    let original_ptr = Arc::as_ptr(&arc1);
+    *get_mut_drop_weak(&mut arc1).unwrap() += 1;
+    assert_eq!(*arc1, 2);
+    assert_eq!(Arc::as_ptr(&arc1), original_ptr);
+}
+
+#[test]
+fn test_rclite_rc_exclusive_and_shared() {
+    let mut rc1 = Rc::new(String::from("a"));
+    let rc2 = Rc::clone(&rc1);
+    get_mut_drop_weak(&mut rc1).unwrap_err();
+    drop(rc2);
+
+    get_mut_drop_weak(&mut rc1).unwrap().push('b');
+    assert_eq!(*rc1, "ab");
+}
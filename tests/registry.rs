@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::WeakRegistry;
+
+#[test]
+fn test_registry_records_origins() {
+    let mut registry = WeakRegistry::new(1);
+    let first = WeakRegistry::downgrade(&mut registry);
+    let line = line!() - 1;
+    let second = WeakRegistry::downgrade(&mut registry);
+
+    assert_eq!(WeakRegistry::live_weaks(&registry), 2);
+    let origins = WeakRegistry::origins(&registry);
+    assert_eq!(origins.len(), 2);
+    assert_eq!(origins[0].file(), file!());
+    assert_eq!(origins[0].line(), line);
+    assert_eq!(origins[1].line(), line + 2);
+
+    assert_eq!(second.origin().line(), line + 2);
+    let clone = first.clone();
+    drop(first);
+    assert_eq!(WeakRegistry::live_weaks(&registry), 2);
+    assert_eq!(WeakRegistry::origins(&registry).len(), 2);
+    // A record goes away with the last clone of its handle.
+    drop(clone);
+    assert_eq!(WeakRegistry::live_weaks(&registry), 1);
+    assert_eq!(WeakRegistry::origins(&registry), [second.origin()]);
+
+    *registry.get_mut_drop_weak().unwrap() += 1;
+
+    assert_eq!(*registry, 2);
+    assert!(second.upgrade().is_none());
+    assert_eq!(WeakRegistry::live_weaks(&registry), 0);
+    assert!(WeakRegistry::origins(&registry).is_empty());
+}
+
+#[test]
+fn test_registry_strong_shared_keeps_records() {
+    let mut registry = WeakRegistry::new(1);
+    let _weak = WeakRegistry::downgrade(&mut registry);
+    let weak = WeakRegistry::downgrade(&mut registry);
+    let _other = weak.upgrade().unwrap();
+
+    assert!(registry.get_mut_drop_weak().is_none());
+    assert_eq!(WeakRegistry::origins(&registry).len(), 2);
+}
+
+#[test]
+fn test_registry_prunes_dropped_handles() {
+    let mut registry = WeakRegistry::new(1);
+    for _ in 0..100 {
+        drop(WeakRegistry::downgrade(&mut registry));
+    }
+    let _weak = WeakRegistry::downgrade(&mut registry);
+    assert_eq!(WeakRegistry::origins(&registry).len(), 1);
+    assert_eq!(WeakRegistry::live_weaks(&registry), 1);
+}
+
+#[test]
+fn test_registry_from_arc_rejects_strongly_shared() {
+    let arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+    let arc = WeakRegistry::from_arc(arc).unwrap_err();
+    drop(other);
+    assert!(WeakRegistry::from_arc(arc).is_ok());
+}
+
+#[test]
+fn test_registry_from_arc_rejects_weakly_shared() {
+    let arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+    let arc = WeakRegistry::from_arc(arc).unwrap_err();
+    drop(weak);
+    assert!(WeakRegistry::from_arc(arc).is_ok());
+}
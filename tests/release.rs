@@ -0,0 +1,64 @@
+#![cfg(all(feature = "event-listener", feature = "std"))]
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::Duration;
+
+use get_mut_drop_weak::ReleaseEvent;
+
+static RELEASED: ReleaseEvent = ReleaseEvent::new();
+
+#[test]
+fn test_release_wakes_blocking_waiter() {
+    let event = ReleaseEvent::new();
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+    let reader = Arc::clone(&arc);
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            thread::sleep(Duration::from_millis(20));
+            event.release(reader);
+        });
+        *event
+            .get_mut_drop_weak_blocking(&mut arc, Duration::from_secs(60))
+            .unwrap() += 1;
+    });
+
+    assert_eq!(*arc, 2);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_release_blocking_times_out() {
+    let event = ReleaseEvent::default();
+    let mut arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+
+    event
+        .get_mut_drop_weak_blocking(&mut arc, Duration::from_millis(10))
+        .unwrap_err();
+    assert!(Arc::ptr_eq(&arc, &other));
+}
+
+#[test]
+fn test_release_resolves_async_waiter() {
+    let mut arc = Arc::new(1);
+    let reader = Arc::clone(&arc);
+
+    {
+        let mut future = pin!(RELEASED.get_mut_drop_weak_async(&mut arc));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+
+        RELEASED.release(reader);
+        let Poll::Ready(Ok(value)) = future.as_mut().poll(&mut cx) else {
+            panic!("still pending after release");
+        };
+        *value += 1;
+    }
+    assert_eq!(*arc, 2);
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::{Relocate, get_mut_drop_weak_dyn, relocate};
+
+trait Counter: Send + Sync {
+    fn bump(&mut self);
+    fn get(&self) -> u32;
+    fn relocate_arc(self: Arc<Self>) -> Result<Arc<dyn Counter>, Arc<dyn Counter>>;
+}
+
+struct Simple(u32);
+
+impl Counter for Simple {
+    fn bump(&mut self) {
+        self.0 += 1;
+    }
+
+    fn get(&self) -> u32 {
+        self.0
+    }
+
+    fn relocate_arc(self: Arc<Self>) -> Result<Arc<dyn Counter>, Arc<dyn Counter>> {
+        relocate(self).map(|a| a as _).map_err(|a| a as _)
+    }
+}
+
+impl Relocate for dyn Counter {
+    fn relocate(this: Arc<Self>) -> Result<Arc<Self>, Arc<Self>> {
+        this.relocate_arc()
+    }
+}
+
+#[test]
+fn test_dyn_weak_shared_drops_weak_success() {
+    let mut arc: Arc<dyn Counter> = Arc::new(Simple(1));
+    let weak = Arc::downgrade(&arc);
+    let original_ptr = Arc::as_ptr(&arc).cast::<()>();
+
+    get_mut_drop_weak_dyn(&mut arc).ok().unwrap().bump();
+
+    assert_eq!(arc.get(), 2);
+    assert_ne!(Arc::as_ptr(&arc).cast::<()>(), original_ptr);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_dyn_strong_shared_no_mut() {
+    let mut arc: Arc<dyn Counter> = Arc::new(Simple(1));
+    let other = Arc::clone(&arc);
+
+    assert!(get_mut_drop_weak_dyn(&mut arc).is_err());
+    assert!(Arc::ptr_eq(&arc, &other));
+}
+
+#[test]
+fn test_relocate_sized_shared_returns_original() {
+    let arc = Arc::new(3);
+    let other = Arc::clone(&arc);
+
+    let arc = relocate(arc).unwrap_err();
+    assert!(Arc::ptr_eq(&arc, &other));
+}
@@ -0,0 +1,116 @@
+use std::sync::{Arc, Weak};
+
+use get_mut_drop_weak::{
+    RepairSelfWeak, update_cyclic, with_exclusive_fixup, with_exclusive_repair,
+};
+
+#[derive(Debug)]
+struct Node {
+    this: Weak<Node>,
+    value: i32,
+}
+
+impl RepairSelfWeak for Node {
+    fn repair(&mut self, new_self: &Weak<Self>) {
+        assert!(new_self.upgrade().is_none());
+        self.this = new_self.clone();
+    }
+}
+
+fn node(value: i32) -> Arc<Node> {
+    Arc::new_cyclic(|this| Node {
+        this: this.clone(),
+        value,
+    })
+}
+
+#[test]
+fn test_repair_self_weak() {
+    let mut arc = node(1);
+    let external = Arc::downgrade(&arc);
+
+    let old = with_exclusive_repair(&mut arc, |node| {
+        // The old self-weak is already orphaned.
+        assert!(node.this.upgrade().is_none());
+        std::mem::replace(&mut node.value, 2)
+    })
+    .unwrap();
+
+    assert_eq!(old, 1);
+    assert_eq!(arc.value, 2);
+    assert!(external.upgrade().is_none());
+    assert!(Arc::ptr_eq(&arc.this.upgrade().unwrap(), &arc));
+}
+
+#[test]
+fn test_repair_strong_shared() {
+    let mut arc = node(1);
+    let other = arc.this.upgrade().unwrap();
+    assert!(with_exclusive_repair(&mut arc, |node| node.value = 2).is_err());
+    assert_eq!(other.value, 1);
+}
+
+#[test]
+fn test_repair_unique_in_place() {
+    let mut arc = Arc::new(Node {
+        this: Weak::new(),
+        value: 1,
+    });
+    let address = Arc::as_ptr(&arc);
+    with_exclusive_repair(&mut arc, |node| node.value = 2).unwrap();
+    assert_eq!(Arc::as_ptr(&arc), address);
+    assert!(arc.this.upgrade().is_none());
+}
+
+#[test]
+fn test_update_cyclic() {
+    let mut arc = node(1);
+    let external = Arc::downgrade(&arc);
+    let mut observers = Vec::new();
+
+    assert!(update_cyclic(&mut arc, |node, new_self| {
+        assert!(new_self.upgrade().is_none());
+        observers.push(new_self.clone());
+        Node {
+            this: new_self.clone(),
+            value: node.value + 1,
+        }
+    }));
+
+    assert_eq!(arc.value, 2);
+    assert!(external.upgrade().is_none());
+    assert!(Arc::ptr_eq(&observers[0].upgrade().unwrap(), &arc));
+    assert!(Arc::ptr_eq(&arc.this.upgrade().unwrap(), &arc));
+}
+
+#[test]
+fn test_update_cyclic_strong_shared() {
+    let mut arc = Arc::new(1);
+    let _other = Arc::clone(&arc);
+    assert!(!update_cyclic(&mut arc, |_, _| unreachable!()));
+}
+
+#[test]
+fn test_fixup_reregisters() {
+    let mut arc = Arc::new(1);
+    let mut registry = [Arc::downgrade(&arc)];
+
+    let result = with_exclusive_fixup(
+        &mut arc,
+        |value| {
+            *value += 1;
+            *value
+        },
+        |_, new_self| registry[0] = new_self.clone(),
+    );
+
+    assert_eq!(result.unwrap(), 2);
+    assert!(Arc::ptr_eq(&registry[0].upgrade().unwrap(), &arc));
+}
+
+#[test]
+fn test_fixup_only_after_replacement() {
+    let mut arc = Arc::new(1);
+    with_exclusive_fixup(&mut arc, |value| *value += 1, |_, _| unreachable!()).unwrap();
+    assert_eq!(*arc, 2);
+}
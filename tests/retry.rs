@@ -0,0 +1,44 @@
+use std::sync::Arc;
+use std::thread;
+
+use get_mut_drop_weak::{ArcGetMutExt, get_mut_drop_weak_retry};
+
+#[test]
+fn test_retry_weak_shared_drops_weak() {
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+
+    *get_mut_drop_weak_retry(&mut arc, 3).unwrap() += 1;
+
+    assert_eq!(*arc, 2);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_retry_strong_shared_fails_immediately() {
+    let mut arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+
+    arc.get_mut_drop_weak_retry(usize::MAX).unwrap_err();
+    assert!(Arc::ptr_eq(&arc, &other));
+}
+
+#[test]
+fn test_retry_zero_attempts_tries_once() {
+    let mut arc = Arc::new(1);
+    *get_mut_drop_weak_retry(&mut arc, 0).unwrap() += 1;
+    assert_eq!(*arc, 2);
+}
+
+#[test]
+fn test_retry_transient_upgrades() {
+    let mut arc = Arc::new(0);
+    let weak = Arc::downgrade(&arc);
+    let upgrader = thread::spawn(move || while weak.upgrade().is_some() {});
+
+    // The first attempt may still see an upgrade as strong sharing.
+    while get_mut_drop_weak_retry(&mut arc, 100).is_err() {}
+
+    upgrader.join().unwrap();
+    assert_eq!(Arc::weak_count(&arc), 0);
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "safe")]
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use get_mut_drop_weak::FailureReason;
+use get_mut_drop_weak::safe::{
+    clone_replace_drop_weak, get_mut_drop_weak, get_mut_drop_weak_detailed, make_mut_drop_weak,
+};
+
+#[test]
+fn test_safe_unique_kept() {
+    let mut arc = Arc::new(1);
+    let ptr = Arc::as_ptr(&arc);
+    *get_mut_drop_weak(&mut arc).unwrap() += 1;
+    assert_eq!(*arc, 2);
+    assert_eq!(Arc::as_ptr(&arc), ptr);
+}
+
+#[test]
+fn test_safe_weakly_shared_drops_weak() {
+    let mut arc = Arc::new(vec![1]);
+    let weak = Arc::downgrade(&arc);
+    get_mut_drop_weak(&mut arc).unwrap().push(2);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(*arc, [1, 2]);
+    assert_eq!(Arc::weak_count(&arc), 0);
+
+    let mut rc = Rc::new(String::from("a"));
+    let weak = Rc::downgrade(&rc);
+    get_mut_drop_weak(&mut rc).unwrap().push('b');
+    assert!(weak.upgrade().is_none());
+    assert_eq!(*rc, "ab");
+}
+
+#[test]
+fn test_safe_strongly_shared_fails() {
+    let mut arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+    let _weak = Arc::downgrade(&arc);
+
+    let err = get_mut_drop_weak_detailed(&mut arc).unwrap_err();
+    assert_eq!(err.reason(), FailureReason::StronglyShared);
+    assert_eq!(err.strong_count(), 2);
+    assert_eq!(err.weak_count(), 1);
+    assert!(Arc::ptr_eq(err.into_inner(), &other));
+}
+
+#[test]
+fn test_safe_clone_variants() {
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+    *clone_replace_drop_weak(&mut arc).unwrap() += 1;
+    assert!(weak.upgrade().is_none());
+
+    let other = Arc::clone(&arc);
+    assert!(clone_replace_drop_weak(&mut arc).is_err());
+    *make_mut_drop_weak(&mut arc) += 1;
+    assert_eq!(*arc, 3);
+    assert_eq!(*other, 2);
+}
@@ -0,0 +1,28 @@
+#![cfg(shuttle)]
+
+use get_mut_drop_weak::get_mut_drop_weak;
+use shuttle::sync::Arc;
+use shuttle::thread;
+
+#[test]
+fn shuttle_weak_upgrade_race() {
+    shuttle::check_random(
+        || {
+            let mut arc = Arc::new(0usize);
+            let weak = Arc::downgrade(&arc);
+
+            let handle = thread::spawn(move || weak.upgrade().map(|strong| *strong));
+
+            // Either the replacement wins (and the upgrade sees nothing), or the upgrade
+            // holds a strong reference and exclusive access is refused.
+            if let Ok(value) = get_mut_drop_weak(&mut arc) {
+                *value += 1;
+            }
+            if let Some(seen) = handle.join().unwrap() {
+                assert_eq!(seen, 0);
+            }
+            assert!(*arc <= 1);
+        },
+        1000,
+    );
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::get_mut_or_insert_with;
+
+#[test]
+fn test_slot_empty_inserts() {
+    let mut slot = None;
+    get_mut_or_insert_with(&mut slot, Vec::new).unwrap().push(1);
+    assert_eq!(slot.as_deref(), Some(&vec![1]));
+}
+
+#[test]
+fn test_slot_unique_kept() {
+    let mut slot = Some(Arc::new(1));
+    let ptr = Arc::as_ptr(slot.as_ref().unwrap());
+
+    *get_mut_or_insert_with(&mut slot, || unreachable!()).unwrap() += 1;
+
+    assert_eq!(slot.as_deref(), Some(&2));
+    assert_eq!(Arc::as_ptr(slot.as_ref().unwrap()), ptr);
+}
+
+#[test]
+fn test_slot_weak_shared_drops_weak() {
+    let mut slot = Some(Arc::new(1));
+    let weak = Arc::downgrade(slot.as_ref().unwrap());
+
+    *get_mut_or_insert_with(&mut slot, || unreachable!()).unwrap() += 1;
+
+    assert_eq!(slot.as_deref(), Some(&2));
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_slot_strong_shared() {
+    let mut slot = Some(Arc::new(1));
+    let other = Arc::clone(slot.as_ref().unwrap());
+
+    let arc = get_mut_or_insert_with(&mut slot, || unreachable!()).unwrap_err();
+    assert!(Arc::ptr_eq(arc, &other));
+}
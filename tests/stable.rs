@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::{FailureReason, get_mut_stable};
+
+#[test]
+fn test_stable_unique() {
+    let mut arc = Arc::new(1);
+    let ptr = Arc::as_ptr(&arc);
+    *get_mut_stable(&mut arc).unwrap() += 1;
+    assert_eq!(*arc, 2);
+    assert_eq!(Arc::as_ptr(&arc), ptr);
+}
+
+#[test]
+fn test_stable_weak_shared_keeps_allocation() {
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+
+    let err = get_mut_stable(&mut arc).unwrap_err();
+    assert_eq!(err.reason(), FailureReason::WouldReplace);
+    assert_eq!((err.strong_count(), err.weak_count()), (1, 1));
+    assert_eq!(
+        err.to_string(),
+        "weak pointers can't be dissociated without a new allocation (strong = 1, weak = 1)"
+    );
+    assert!(weak.upgrade().is_some());
+}
+
+#[test]
+fn test_stable_strong_shared() {
+    let mut arc = Arc::new(1);
+    let _other = Arc::clone(&arc);
+    let err = get_mut_stable(&mut arc).unwrap_err();
+    assert_eq!(err.reason(), FailureReason::StronglyShared);
+}
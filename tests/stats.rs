@@ -0,0 +1,298 @@
+#![cfg(feature = "stats")]
+
+use std::any::Any;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use get_mut_drop_weak::{
+    ArcPool, Relocate, Stats, clone_replace_drop_weak, get_mut_drop_weak, get_mut_drop_weak_any,
+    get_mut_drop_weak_copy, get_mut_drop_weak_dyn, get_mut_drop_weak_pooled,
+    get_mut_drop_weak_uninit, get_mut_drop_weak_uninit_slice, get_mut_drop_weak_unsized,
+    get_mut_or_insert_with, into_box_drop_weak, make_mut_drop_weak, relocate, replace_drop_weak,
+    reset_stats, stats, take_drop_weak, try_get_mut_drop_weak, try_update, update_cyclic,
+};
+
+trait Counter {
+    fn relocate_arc(self: Arc<Self>) -> Result<Arc<dyn Counter>, Arc<dyn Counter>>;
+}
+
+impl Counter for u32 {
+    fn relocate_arc(self: Arc<Self>) -> Result<Arc<dyn Counter>, Arc<dyn Counter>> {
+        relocate(self).map(|a| a as _).map_err(|a| a as _)
+    }
+}
+
+impl Relocate for dyn Counter {
+    fn relocate(this: Arc<Self>) -> Result<Arc<Self>, Arc<Self>> {
+        this.relocate_arc()
+    }
+}
+
+/// An allocator-parameterized `Arc` that allocates its replacements in the
+/// global allocator.
+#[cfg(feature = "allocator-api2")]
+#[derive(Clone)]
+struct GlobalArc(Arc<u32>);
+
+#[cfg(feature = "allocator-api2")]
+unsafe impl get_mut_drop_weak::allocator_api2::AllocatorArc<u32> for GlobalArc {
+    type Allocator = allocator_api2::alloc::Global;
+    type Uninit = Arc<std::mem::MaybeUninit<u32>>;
+
+    fn as_ptr(this: &Self) -> *const u32 {
+        Arc::as_ptr(&this.0)
+    }
+
+    fn strong_count(this: &Self) -> usize {
+        Arc::strong_count(&this.0)
+    }
+
+    fn weak_count(this: &Self) -> usize {
+        Arc::weak_count(&this.0)
+    }
+
+    fn is_unique(this: &mut Self) -> bool {
+        Arc::get_mut(&mut this.0).is_some()
+    }
+
+    fn try_unwrap(this: Self) -> Result<u32, Self> {
+        Arc::try_unwrap(this.0).map_err(GlobalArc)
+    }
+
+    fn new_uninit_in(_alloc: Self::Allocator) -> Self::Uninit {
+        Arc::new_uninit()
+    }
+
+    unsafe fn init(mut uninit: Self::Uninit, value: u32) -> Self {
+        Arc::get_mut(&mut uninit).unwrap().write(value);
+        GlobalArc(unsafe { uninit.assume_init() })
+    }
+}
+
+/// The change in `[fast_path, replacements, clones, failures]` caused by
+/// calling `f` on an exclusive pointer, a weakly shared one and a strongly
+/// shared one, in turn.
+fn deltas<P: Clone>(
+    new: impl Fn() -> P,
+    downgrade: impl Fn(&P) -> Box<dyn Any>,
+    mut f: impl FnMut(&mut P),
+) -> [usize; 4] {
+    let before = stats();
+
+    f(&mut new());
+
+    let mut weakly_shared = new();
+    let _weak = downgrade(&weakly_shared);
+    f(&mut weakly_shared);
+
+    let mut strongly_shared = new();
+    let _other = strongly_shared.clone();
+    f(&mut strongly_shared);
+
+    let after = stats();
+    [
+        after.fast_path - before.fast_path,
+        after.replacements - before.replacements,
+        after.clones - before.clones,
+        after.failures - before.failures,
+    ]
+}
+
+fn arc_deltas(f: impl FnMut(&mut Arc<u32>)) -> [usize; 4] {
+    deltas(|| Arc::new(1), |arc| Box::new(Arc::downgrade(arc)), f)
+}
+
+// The counters are global, so everything is checked in a single test.
+#[test]
+fn test_stats() {
+    reset_stats();
+
+    let mut arc = Arc::new(1);
+    get_mut_drop_weak(&mut arc).unwrap();
+
+    let _weak = Arc::downgrade(&arc);
+    get_mut_drop_weak(&mut arc).unwrap();
+
+    let other = Arc::clone(&arc);
+    get_mut_drop_weak(&mut arc).unwrap_err();
+    *make_mut_drop_weak(&mut arc) += 1;
+    assert_eq!(*other, 1);
+
+    let expected = Stats::default();
+    let snapshot = stats();
+    assert_eq!(snapshot.fast_path, 1);
+    assert_eq!(snapshot.replacements, 1);
+    assert_eq!(snapshot.clones, 1);
+    // `make_mut_drop_weak` fails before falling back to a clone.
+    assert_eq!(snapshot.failures, 2);
+
+    reset_stats();
+    assert_eq!(stats(), expected);
+
+    // Each path reports one fast path, one replacement (or a clone, for the
+    // paths that copy the value instead of moving it) and one failure.
+    let moved = [1, 1, 0, 1];
+    let copied = [1, 0, 1, 1];
+    assert_eq!(
+        arc_deltas(|arc| {
+            let _ = try_get_mut_drop_weak(arc);
+        }),
+        moved
+    );
+    assert_eq!(
+        arc_deltas(|arc| {
+            let _ = take_drop_weak(arc);
+        }),
+        moved
+    );
+    assert_eq!(
+        arc_deltas(|arc| {
+            let _ = replace_drop_weak(arc, 2);
+        }),
+        moved
+    );
+    assert_eq!(
+        arc_deltas(|arc| {
+            let _ = get_mut_drop_weak_copy(arc);
+        }),
+        copied
+    );
+    assert_eq!(
+        arc_deltas(|arc| {
+            let _ = clone_replace_drop_weak(arc);
+        }),
+        copied
+    );
+    assert_eq!(
+        arc_deltas(|arc| {
+            let _ = try_update(arc, |value| Ok::<_, (u32, ())>(value + 1));
+        }),
+        moved
+    );
+    assert_eq!(
+        arc_deltas(|arc| {
+            assert_ne!(try_update(arc, |value| Err((value, ()))), Ok(()));
+        }),
+        moved
+    );
+    assert_eq!(
+        deltas(
+            || Some(Arc::new(1_u32)),
+            |slot| Box::new(Arc::downgrade(slot.as_ref().unwrap())),
+            |slot| {
+                let _ = into_box_drop_weak(slot.take().unwrap());
+            },
+        ),
+        moved
+    );
+    assert_eq!(
+        deltas(
+            || Some(Arc::new(1_u32)),
+            |slot| Box::new(Arc::downgrade(slot.as_ref().unwrap())),
+            |slot| {
+                let _ = get_mut_or_insert_with(slot, || 2);
+            },
+        ),
+        moved
+    );
+    // Filling an empty slot hands out a fresh, exclusive pointer.
+    assert_eq!(
+        deltas(
+            || None::<Arc<u32>>,
+            |_| Box::new(()),
+            |slot| {
+                let _ = get_mut_or_insert_with(slot, || 2);
+            },
+        ),
+        [3, 0, 0, 0]
+    );
+    assert_eq!(
+        deltas(
+            Arc::<u32>::new_uninit,
+            |arc| Box::new(Arc::downgrade(arc)),
+            |arc| {
+                let _ = get_mut_drop_weak_uninit(arc);
+            },
+        ),
+        moved
+    );
+    let pool = ArcPool::new(1);
+    assert_eq!(
+        arc_deltas(|arc| {
+            let _ = get_mut_drop_weak_pooled(arc, &pool);
+        }),
+        moved
+    );
+    // `update_cyclic` always moves the value, so its fast path is a replacement.
+    assert_eq!(
+        arc_deltas(|arc| {
+            let _ = update_cyclic(arc, |value, _| value);
+        }),
+        [0, 2, 0, 1]
+    );
+    assert_eq!(
+        deltas(
+            || Arc::new(1_u32) as Arc<dyn Any + Send + Sync>,
+            |arc| Box::new(Arc::downgrade(arc)),
+            |arc| {
+                let _ = get_mut_drop_weak_any::<u32>(arc);
+            },
+        ),
+        moved
+    );
+    assert_eq!(
+        deltas(
+            || Arc::new(1_u32) as Arc<dyn Counter>,
+            |arc| Box::new(Arc::downgrade(arc)),
+            |arc| {
+                let _ = get_mut_drop_weak_dyn(arc);
+            },
+        ),
+        moved
+    );
+    assert_eq!(
+        deltas(
+            || Arc::<str>::from("a"),
+            |arc| Box::new(Arc::downgrade(arc)),
+            |arc| {
+                let _ = get_mut_drop_weak_unsized(arc);
+            },
+        ),
+        copied
+    );
+    assert_eq!(
+        deltas(
+            || Arc::<[u8]>::new_uninit_slice(1),
+            |arc| Box::new(Arc::downgrade(arc)),
+            |arc| {
+                // SAFETY: The weak pointers are never upgraded.
+                let _ = unsafe { get_mut_drop_weak_uninit_slice(arc) };
+            },
+        ),
+        copied
+    );
+    #[cfg(feature = "allocator-api2")]
+    assert_eq!(
+        deltas(
+            || GlobalArc(Arc::new(1)),
+            |arc| Box::new(Arc::downgrade(&arc.0)),
+            |arc| {
+                let _ = get_mut_drop_weak::allocator_api2::get_mut_drop_weak_in(
+                    arc,
+                    allocator_api2::alloc::Global,
+                );
+            },
+        ),
+        moved
+    );
+    assert_eq!(
+        deltas(
+            || Rc::new(1),
+            |rc| Box::new(Rc::downgrade(rc)),
+            |rc| {
+                let _ = get_mut_drop_weak::rc::get_mut_drop_weak(rc);
+            },
+        ),
+        moved
+    );
+}
@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use get_mut_drop_weak::{
+    ArcGetMutExt, NoSelfWeak, OrphanWeaks, get_mut_drop_weak_authorized, get_mut_drop_weak_strict,
+};
+
+struct Config {
+    name: String,
+}
+
+impl NoSelfWeak for Config {}
+
+#[test]
+fn test_strict_weak_shared_drops_weak() {
+    let mut arc = Arc::new(Config {
+        name: String::from("a"),
+    });
+    let weak = Arc::downgrade(&arc);
+
+    get_mut_drop_weak_strict(&mut arc)
+        .ok()
+        .unwrap()
+        .name
+        .push('b');
+
+    assert_eq!(arc.name, "ab");
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_strict_primitives() {
+    let mut arc = Arc::new(1);
+    let _other = Arc::clone(&arc);
+    assert!(arc.get_mut_drop_weak_strict().is_err());
+
+    let mut text = Arc::new(String::new());
+    text.get_mut_drop_weak_strict().unwrap().push('a');
+    assert_eq!(*text, "a");
+}
+
+#[test]
+fn test_strict_containers() {
+    let mut arc = Arc::new((
+        vec![Some(Box::new(1))],
+        [0u8; 4],
+        BTreeMap::<String, u32>::new(),
+    ));
+    let weak = Arc::downgrade(&arc);
+
+    let (list, bytes, map) = get_mut_drop_weak_strict(&mut arc).ok().unwrap();
+    list.push(None);
+    bytes[0] = 1;
+    map.insert(String::from("a"), 1);
+
+    assert_eq!(arc.0.len(), 2);
+    assert!(weak.upgrade().is_none());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_strict_hash_map() {
+    let mut arc = Arc::new(std::collections::HashMap::from([(1, 10)]));
+    let _weak = Arc::downgrade(&arc);
+    get_mut_drop_weak_strict(&mut arc)
+        .ok()
+        .unwrap()
+        .insert(2, 20);
+    assert_eq!(arc.len(), 2);
+}
+
+#[test]
+fn test_authorized_drops_weak() {
+    let allowed = OrphanWeaks::allow_orphaning_weak_pointers();
+    let mut arc = Arc::new(1);
+    let weak = Arc::downgrade(&arc);
+
+    *get_mut_drop_weak_authorized(&mut arc, allowed).unwrap() += 1;
+
+    assert_eq!(*arc, 2);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(size_of::<OrphanWeaks>(), 0);
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::{ArcGetMutExt, replace_drop_weak, take_drop_weak};
+
+#[test]
+fn test_take_weak_shared_leaves_default() {
+    let mut arc = Arc::new(vec![1, 2, 3]);
+    let weak = Arc::downgrade(&arc);
+
+    assert_eq!(take_drop_weak(&mut arc), Some(vec![1, 2, 3]));
+
+    assert!(arc.is_empty());
+    assert_eq!(Arc::weak_count(&arc), 0);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_take_exclusive_in_place() {
+    let mut arc = Arc::new(String::from("state"));
+    let original_ptr = Arc::as_ptr(&arc);
+
+    assert_eq!(arc.take_drop_weak().as_deref(), Some("state"));
+    assert_eq!(*arc, "");
+    assert_eq!(Arc::as_ptr(&arc), original_ptr);
+}
+
+#[test]
+fn test_take_strong_shared_none() {
+    let mut arc = Arc::new(5);
+    let other = Arc::clone(&arc);
+
+    assert_eq!(take_drop_weak(&mut arc), None);
+    assert_eq!(*arc, 5);
+    assert!(Arc::ptr_eq(&arc, &other));
+}
+
+#[test]
+fn test_replace_weak_shared_returns_old() {
+    let mut arc = Arc::new(String::from("old"));
+    let weak = Arc::downgrade(&arc);
+    let original_ptr = Arc::as_ptr(&arc);
+
+    let old = replace_drop_weak(&mut arc, String::from("new")).unwrap();
+
+    assert_eq!(old, "old");
+    assert_eq!(*arc, "new");
+    assert_ne!(Arc::as_ptr(&arc), original_ptr);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_replace_strong_shared_hands_back_new() {
+    let mut arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+
+    assert_eq!(arc.replace_drop_weak(2), Err(2));
+    assert_eq!(*arc, 1);
+    assert!(Arc::ptr_eq(&arc, &other));
+}
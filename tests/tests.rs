@@ -226,3 +226,50 @@ fn simple_multithreaded() {
         .unwrap();
     });
 }
+
+#[test]
+fn generic_over_pointer_type() {
+    use get_mut_drop_weak::SharedPointer;
+    use std::rc::Rc;
+
+    fn bump<P: SharedPointer<usize>>(ptr: &mut P) -> bool {
+        match get_mut_drop_weak(ptr) {
+            Ok(value) => {
+                *value += 1;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    let mut arc = Arc::new(1usize);
+    let weak_arc = Arc::downgrade(&arc);
+    assert!(bump(&mut arc));
+    assert_eq!(*arc, 2);
+    assert!(weak_arc.upgrade().is_none());
+
+    let mut rc = Rc::new(1usize);
+    let weak_rc = Rc::downgrade(&rc);
+    assert!(bump(&mut rc));
+    assert_eq!(*rc, 2);
+    assert!(weak_rc.upgrade().is_none());
+
+    let rc2 = Rc::clone(&rc);
+    assert!(!bump(&mut rc));
+    drop(rc2);
+}
+
+#[test]
+fn extension_trait_method_syntax() {
+    use get_mut_drop_weak::ArcGetMutExt;
+
+    let mut arc = Arc::new(vec![1]);
+    let weak = Arc::downgrade(&arc);
+    arc.get_mut_drop_weak().unwrap().push(2);
+    assert_eq!(*arc, vec![1, 2]);
+    assert!(weak.upgrade().is_none());
+
+    let arc2 = Arc::clone(&arc);
+    assert!(ptr::eq(arc.get_mut_drop_weak().unwrap_err(), &arc));
+    drop(arc2);
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "tokio")]
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
+
+use get_mut_drop_weak::tokio::{OwnOrLock, lock_or_own};
+use tokio::sync::Mutex;
+
+/// Polls `future` once.
+fn poll_once<F: Future>(future: F) -> Poll<F::Output> {
+    let future = pin!(future);
+    future.poll(&mut Context::from_waker(Waker::noop()))
+}
+
+#[test]
+fn test_tokio_lock_or_own_unique_skips_lock() {
+    let mut arc = Arc::new(Mutex::new(1));
+    let ptr = Arc::as_ptr(&arc);
+
+    let Poll::Ready(mut value) = poll_once(lock_or_own(&mut arc)) else {
+        panic!("pending");
+    };
+    assert!(matches!(value, OwnOrLock::Own(_)));
+    *value += 1;
+    drop(value);
+
+    assert_eq!(*arc.try_lock().unwrap(), 2);
+    assert_eq!(Arc::as_ptr(&arc), ptr);
+}
+
+#[test]
+fn test_tokio_lock_or_own_weak_shared_drops_weak() {
+    let mut arc = Arc::new(Mutex::new(1));
+    let weak = Arc::downgrade(&arc);
+
+    let Poll::Ready(mut value) = poll_once(lock_or_own(&mut arc)) else {
+        panic!("pending");
+    };
+    assert!(matches!(value, OwnOrLock::Own(_)));
+    *value += 1;
+    drop(value);
+
+    assert!(weak.upgrade().is_none());
+    assert_eq!(*arc.try_lock().unwrap(), 2);
+}
+
+#[test]
+fn test_tokio_lock_or_own_strong_shared_awaits_lock() {
+    let mut arc = Arc::new(Mutex::new(1));
+    let other = Arc::clone(&arc);
+
+    let guard = other.try_lock().unwrap();
+    assert!(poll_once(lock_or_own(&mut arc)).is_pending());
+    drop(guard);
+
+    let Poll::Ready(mut value) = poll_once(lock_or_own(&mut arc)) else {
+        panic!("pending");
+    };
+    assert!(matches!(value, OwnOrLock::Locked(_)));
+    *value += 1;
+    assert!(other.try_lock().is_err());
+    drop(value);
+
+    assert_eq!(*other.try_lock().unwrap(), 2);
+}
@@ -0,0 +1,99 @@
+#![cfg(all(feature = "tracing", feature = "std"))]
+
+use std::sync::{Arc, Mutex};
+
+use get_mut_drop_weak::get_mut_drop_weak;
+use tracing::{
+    Event, Metadata, Subscriber,
+    field::{Field, Visit},
+    span,
+};
+
+/// Records the message and level of every event.
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<(tracing::Level, String, Option<u64>)>>,
+}
+
+struct MessageVisitor {
+    message: String,
+    weak: Option<u64>,
+}
+
+impl Visit for MessageVisitor {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "weak" {
+            self.weak = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        }
+    }
+}
+
+impl Subscriber for &'static Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = MessageVisitor {
+            message: String::new(),
+            weak: None,
+        };
+        event.record(&mut visitor);
+        self.events.lock().unwrap().push((
+            *event.metadata().level(),
+            visitor.message,
+            visitor.weak,
+        ));
+    }
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+}
+
+#[test]
+fn test_tracing_events() {
+    let recorder: &'static Recorder = Box::leak(Box::default());
+    tracing::subscriber::with_default(recorder, || {
+        let mut arc = Arc::new(1);
+        get_mut_drop_weak(&mut arc).unwrap();
+
+        let _weak1 = Arc::downgrade(&arc);
+        let _weak2 = Arc::downgrade(&arc);
+        get_mut_drop_weak(&mut arc).unwrap();
+
+        let _other = Arc::clone(&arc);
+        get_mut_drop_weak(&mut arc).unwrap_err();
+    });
+
+    let events = recorder.events.lock().unwrap();
+    assert_eq!(
+        *events,
+        [
+            (
+                tracing::Level::DEBUG,
+                String::from("replaced the allocation, orphaning weak pointers"),
+                Some(2)
+            ),
+            (
+                tracing::Level::TRACE,
+                String::from("value is strongly shared"),
+                Some(0)
+            ),
+        ]
+    );
+}
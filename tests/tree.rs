@@ -0,0 +1,70 @@
+use std::sync::{Arc, Mutex, Weak};
+
+use get_mut_drop_weak::tree::{Children, with_exclusive_reparent};
+
+#[derive(Debug)]
+struct Node {
+    name: String,
+    parent: Mutex<Weak<Node>>,
+    children: Vec<Arc<Node>>,
+}
+
+impl Node {
+    fn new(name: &str) -> Arc<Self> {
+        Arc::new(Node {
+            name: name.into(),
+            parent: Mutex::new(Weak::new()),
+            children: Vec::new(),
+        })
+    }
+
+    fn parent(&self) -> Option<Arc<Node>> {
+        self.parent.lock().unwrap().upgrade()
+    }
+}
+
+impl Children for Node {
+    type Child = Arc<Node>;
+
+    fn children(&self) -> impl Iterator<Item = &Arc<Node>> {
+        self.children.iter()
+    }
+
+    fn set_parent(child: &Arc<Node>, parent: &Weak<Node>) {
+        *child.parent.lock().unwrap() = parent.clone();
+    }
+}
+
+#[test]
+fn test_reparent_children() {
+    let mut root = Node::new("root");
+    let child = Node::new("child");
+    with_exclusive_reparent(&mut root, |root| root.children.push(Arc::clone(&child))).unwrap();
+    assert!(Arc::ptr_eq(&child.parent().unwrap(), &root));
+
+    let external = Arc::downgrade(&root);
+    let added = Node::new("added");
+    with_exclusive_reparent(&mut root, |root| {
+        root.name.push('!');
+        root.children.push(Arc::clone(&added));
+    })
+    .unwrap();
+
+    assert_eq!(root.name, "root!");
+    assert!(external.upgrade().is_none());
+    assert!(Arc::ptr_eq(&child.parent().unwrap(), &root));
+    assert!(Arc::ptr_eq(&added.parent().unwrap(), &root));
+}
+
+#[test]
+fn test_reparent_strong_shared() {
+    let mut root = Node::new("root");
+    let child = Node::new("child");
+    with_exclusive_reparent(&mut root, |root| root.children.push(Arc::clone(&child))).unwrap();
+
+    let held = child.parent().unwrap();
+    assert!(with_exclusive_reparent(&mut root, |_| ()).is_err());
+    drop(held);
+    assert!(with_exclusive_reparent(&mut root, |_| ()).is_ok());
+    assert!(Arc::ptr_eq(&child.parent().unwrap(), &root));
+}
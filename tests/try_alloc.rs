@@ -0,0 +1,75 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use get_mut_drop_weak::{FailureReason, TryGetMutError, try_get_mut_drop_weak};
+
+// Fails allocations of exactly `FAIL_SIZE` bytes, so that other tests running
+// in parallel are unaffected.
+struct FailingAlloc;
+
+static FAIL_SIZE: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for FailingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.size() == FAIL_SIZE.load(Ordering::Relaxed) {
+            return std::ptr::null_mut();
+        }
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: FailingAlloc = FailingAlloc;
+
+#[test]
+fn test_try_weak_shared_success() {
+    let mut arc = Arc::new(vec![1]);
+    let weak = Arc::downgrade(&arc);
+
+    try_get_mut_drop_weak(&mut arc).unwrap().push(2);
+    assert_eq!(*arc, [1, 2]);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_try_strong_shared() {
+    let mut arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+
+    match try_get_mut_drop_weak(&mut arc) {
+        Err(TryGetMutError::Shared(err)) => {
+            assert_eq!(err.reason(), FailureReason::StronglyShared);
+            assert_eq!(
+                err.to_string(),
+                "value is strongly shared (strong = 2, weak = 0)"
+            );
+            assert!(Arc::ptr_eq(err.into_inner(), &other));
+        }
+        other => panic!("expected Shared, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_try_alloc_failed_leaves_pointer_untouched() {
+    // An odd size that nothing else in this binary allocates.
+    let mut arc = Arc::new([7u8; 1237]);
+    let weak = Arc::downgrade(&arc);
+    let original_ptr = Arc::as_ptr(&arc);
+
+    // The allocation holds the two counts followed by the value.
+    let (layout, _) = Layout::new::<[usize; 2]>()
+        .extend(Layout::new::<[u8; 1237]>())
+        .unwrap();
+    FAIL_SIZE.store(layout.pad_to_align().size(), Ordering::Relaxed);
+    let result = try_get_mut_drop_weak(&mut arc);
+    assert!(matches!(result, Err(TryGetMutError::AllocFailed(_))));
+    FAIL_SIZE.store(0, Ordering::Relaxed);
+
+    assert_eq!(Arc::as_ptr(&arc), original_ptr);
+    assert!(weak.upgrade().is_some());
+}
@@ -0,0 +1,58 @@
+use std::mem::MaybeUninit;
+use std::sync::Arc;
+
+use get_mut_drop_weak::{get_mut_drop_weak_uninit, get_mut_drop_weak_uninit_slice};
+
+#[test]
+fn test_uninit_weak_shared_drops_weak_success() {
+    let mut arc: Arc<MaybeUninit<String>> = Arc::new_uninit();
+    let weak = Arc::downgrade(&arc);
+    let original_ptr = Arc::as_ptr(&arc);
+
+    get_mut_drop_weak_uninit(&mut arc)
+        .unwrap()
+        .write(String::from("filled"));
+
+    assert_ne!(Arc::as_ptr(&arc), original_ptr);
+    assert!(weak.upgrade().is_none());
+    let arc = unsafe { arc.assume_init() };
+    assert_eq!(*arc, "filled");
+}
+
+#[test]
+fn test_uninit_strong_shared_no_mut() {
+    let mut arc: Arc<MaybeUninit<u64>> = Arc::new_uninit();
+    let other = Arc::clone(&arc);
+
+    get_mut_drop_weak_uninit(&mut arc).unwrap_err();
+    drop(other);
+}
+
+#[test]
+fn test_uninit_slice_weak_shared_copies_prefix() {
+    let mut arc = Arc::<[u8]>::new_uninit_slice(4);
+    Arc::get_mut(&mut arc).unwrap()[..2]
+        .copy_from_slice(&[MaybeUninit::new(1), MaybeUninit::new(2)]);
+    let weak = Arc::downgrade(&arc);
+    let original_ptr = Arc::as_ptr(&arc).cast::<u8>();
+
+    // SAFETY: `weak` is never upgraded.
+    let buf = unsafe { get_mut_drop_weak_uninit_slice(&mut arc) }.unwrap();
+    buf[2].write(3);
+    buf[3].write(4);
+
+    assert_ne!(Arc::as_ptr(&arc).cast::<u8>(), original_ptr);
+    assert!(weak.upgrade().is_none());
+    let arc = unsafe { arc.assume_init() };
+    assert_eq!(*arc, [1, 2, 3, 4]);
+}
+
+#[test]
+fn test_uninit_slice_strong_shared_no_mut() {
+    let mut arc = Arc::<[String]>::new_uninit_slice(2);
+    let other = Arc::clone(&arc);
+
+    // SAFETY: Nothing is copied while the slice is strongly shared.
+    unsafe { get_mut_drop_weak_uninit_slice(&mut arc) }.unwrap_err();
+    assert!(Arc::ptr_eq(&arc, &other));
+}
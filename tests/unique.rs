@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::{UniqueArc, into_unique};
+
+#[test]
+fn test_into_unique_weak_shared_success() {
+    let arc = Arc::new(vec![1]);
+    let weak = Arc::downgrade(&arc);
+
+    let mut unique = into_unique(arc).unwrap();
+    unique.push(2);
+    unique.push(3);
+    assert!(weak.upgrade().is_none());
+
+    let arc = UniqueArc::into_arc(unique);
+    assert_eq!(*arc, vec![1, 2, 3]);
+    assert_eq!(Arc::strong_count(&arc), 1);
+}
+
+#[test]
+fn test_into_unique_strong_shared_returns_arc() {
+    let arc = Arc::new(1);
+    let other = Arc::clone(&arc);
+
+    let arc = into_unique(arc).err().unwrap();
+    assert!(Arc::ptr_eq(&arc, &other));
+}
+
+#[test]
+fn test_unique_arc_build_share_reclaim() {
+    // Build exclusively.
+    let mut unique = UniqueArc::new(String::from("hello"));
+    unique.push_str(", world");
+
+    // Share, including with a weak observer.
+    let arc: Arc<String> = unique.into();
+    let clone = Arc::clone(&arc);
+    let weak = Arc::downgrade(&arc);
+
+    // Can't reclaim while strongly shared.
+    let arc = UniqueArc::from_arc(arc).unwrap_err();
+    drop(clone);
+
+    // Reclaim, orphaning the weak observer.
+    let mut unique = UniqueArc::from_arc(arc).unwrap();
+    assert!(weak.upgrade().is_none());
+    unique.push('!');
+    assert_eq!(*UniqueArc::into_arc(unique), "hello, world!");
+}
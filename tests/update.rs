@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use get_mut_drop_weak::update_with;
+use get_mut_drop_weak::{UpdateError, try_update};
+
+#[derive(Clone, Debug, PartialEq)]
+enum State {
+    Idle,
+    Running(u32),
+}
+
+fn start(state: State) -> Result<State, (State, &'static str)> {
+    match state {
+        State::Idle => Ok(State::Running(1)),
+        other => Err((other, "already running")),
+    }
+}
+
+#[test]
+fn test_try_update_exclusive_in_place() {
+    let mut arc = Arc::new(State::Idle);
+    let original_ptr = Arc::as_ptr(&arc);
+
+    assert_eq!(try_update(&mut arc, start), Ok(()));
+    assert_eq!(*arc, State::Running(1));
+    // No weak pointers, so the allocation is reused.
+    assert_eq!(Arc::as_ptr(&arc), original_ptr);
+
+    assert_eq!(
+        try_update(&mut arc, start),
+        Err(UpdateError::Failed("already running"))
+    );
+    assert_eq!(*arc, State::Running(1));
+    assert_eq!(Arc::as_ptr(&arc), original_ptr);
+}
+
+#[test]
+fn test_try_update_weak_shared_success_orphans_weak() {
+    let mut arc = Arc::new(State::Idle);
+    let weak = Arc::downgrade(&arc);
+    let original_ptr = Arc::as_ptr(&arc);
+
+    assert_eq!(try_update(&mut arc, start), Ok(()));
+    assert_eq!(*arc, State::Running(1));
+    assert_ne!(Arc::as_ptr(&arc), original_ptr);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_try_update_weak_shared_failure_keeps_value() {
+    let mut arc = Arc::new(State::Running(7));
+    let weak = Arc::downgrade(&arc);
+
+    assert_eq!(
+        try_update(&mut arc, start),
+        Err(UpdateError::Failed("already running"))
+    );
+    // The value handed back is kept, but the weak pointer was orphaned first.
+    assert_eq!(*arc, State::Running(7));
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_try_update_without_clone() {
+    struct Token(u32);
+
+    let mut arc = Arc::new(Token(1));
+    let _weak = Arc::downgrade(&arc);
+
+    assert_eq!(
+        try_update(&mut arc, |Token(n)| Ok::<_, (Token, ())>(Token(n + 1))),
+        Ok(())
+    );
+    assert_eq!(arc.0, 2);
+}
+
+#[test]
+fn test_try_update_strong_shared_not_called() {
+    let mut arc = Arc::new(State::Idle);
+    let other = Arc::clone(&arc);
+
+    let result = try_update(&mut arc, |_| -> Result<State, (State, ())> {
+        panic!("closure must not be called")
+    });
+    assert_eq!(result, Err(UpdateError::Shared));
+    assert!(Arc::ptr_eq(&arc, &other));
+}
+
+#[test]
+fn test_update_with_weak_shared() {
+    let mut arc = Arc::new(State::Running(1));
+    let weak = Arc::downgrade(&arc);
+
+    assert!(update_with(&mut arc, |state| match state {
+        State::Idle => State::Running(0),
+        State::Running(n) => State::Running(n + 1),
+    }));
+    assert_eq!(*arc, State::Running(2));
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_update_with_strong_shared() {
+    let mut arc = Arc::new(State::Idle);
+    let _other = Arc::clone(&arc);
+
+    assert!(!update_with(&mut arc, |_| panic!(
+        "closure must not be called"
+    )));
+    assert_eq!(*arc, State::Idle);
+}
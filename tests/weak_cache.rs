@@ -0,0 +1,45 @@
+#![cfg(feature = "std")]
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+};
+
+use get_mut_drop_weak::refresh_cache_entry;
+
+#[test]
+fn test_refresh_cache_entry_reinserts() {
+    let mut arc = Arc::new(String::from("a"));
+    let mut cache = HashMap::new();
+    cache.insert("key", Arc::downgrade(&arc));
+    let stale = Arc::downgrade(&arc);
+
+    refresh_cache_entry(&mut cache, "key", &mut arc)
+        .unwrap()
+        .push('b');
+
+    assert!(stale.upgrade().is_none());
+    let cached = cache["key"].upgrade().unwrap();
+    assert!(Arc::ptr_eq(&cached, &arc));
+    assert_eq!(*cached, "ab");
+}
+
+#[test]
+fn test_refresh_cache_entry_inserts_missing() {
+    let mut arc = Arc::new(1);
+    let mut cache: HashMap<&str, Weak<i32>> = HashMap::new();
+
+    *refresh_cache_entry(&mut cache, "key", &mut arc).unwrap() += 1;
+
+    assert_eq!(*cache["key"].upgrade().unwrap(), 2);
+}
+
+#[test]
+fn test_refresh_cache_entry_strong_shared() {
+    let mut arc = Arc::new(1);
+    let _other = Arc::clone(&arc);
+    let mut cache: HashMap<&str, Weak<i32>> = HashMap::new();
+
+    assert!(refresh_cache_entry(&mut cache, "key", &mut arc).is_err());
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use get_mut_drop_weak::get_mut_drop_weak;
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug)]
+struct Token;
+
+impl Drop for Token {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn test_zst_weak_shared_drops_weak() {
+    let mut arc = Arc::new(Token);
+    let weak = Arc::downgrade(&arc);
+
+    get_mut_drop_weak(&mut arc).unwrap();
+
+    assert!(weak.upgrade().is_none());
+    assert_eq!(Arc::weak_count(&arc), 0);
+    // The token was moved, not duplicated.
+    assert_eq!(DROPS.load(Ordering::Relaxed), 0);
+    drop(arc);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_zst_strong_shared_no_mut() {
+    let mut arc = Arc::new(());
+    let other = Arc::clone(&arc);
+
+    get_mut_drop_weak(&mut arc).unwrap_err();
+    assert!(Arc::ptr_eq(&arc, &other));
+}